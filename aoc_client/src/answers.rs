@@ -0,0 +1,68 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{error::Error, lines};
+
+/// Known correct answers for a year, stored as [`lines`] of tab-separated day, part and answer.
+/// New answers are appended and the last line for a part wins.
+pub struct Answers {
+    path: PathBuf,
+    answers: BTreeMap<(u32, u8), String>,
+}
+
+impl Answers {
+    /// Loads the answers stored at `path`, which need not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let answers = lines::load(&path, parse_answer)?.into_iter().collect();
+        Ok(Answers { path, answers })
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Stores an answer, replacing any previous answer for the part, by appending it to the file.
+    pub fn set(&mut self, day: u32, part: u8, answer: &str) -> Result<(), Error> {
+        lines::append(&self.path, "", &format!("{day}\t{part}\t{answer}\n"))?;
+        self.answers.insert((day, part), answer.to_string());
+        Ok(())
+    }
+}
+
+fn parse_answer(line: &str) -> Option<((u32, u8), String)> {
+    let mut fields = line.splitn(3, '\t');
+    let day = fields.next()?.trim().parse().ok()?;
+    let part = fields.next()?.trim().parse().ok()?;
+    let answer = fields.next()?.trim().to_string();
+    Some(((day, part), answer))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answers;
+
+    #[test]
+    fn roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+
+        let mut answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get(5, 1), None);
+        answers.set(5, 2, "46").unwrap();
+        answers.set(5, 1, "35").unwrap();
+        answers.set(5, 1, "36").unwrap();
+
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get(5, 1), Some("36"));
+        assert_eq!(answers.get(5, 2), Some("46"));
+
+        std::fs::write(&path, "# day 5\n5\t1\t35\nday 6: not sure yet").unwrap();
+        let mut answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get(5, 1), Some("35"));
+        answers.set(5, 2, "46").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# day 5\n5\t1\t35\nday 6: not sure yet\n5\t2\t46\n"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The first year Advent of Code ran.
+pub const FIRST_YEAR: u32 = 2015;
+/// The last day of each event.
+pub const LAST_DAY: u32 = 25;
+
+/// Puzzles unlock at midnight US-Eastern, which is five hours behind UTC in December.
+const UNLOCK_OFFSET_SECS: u64 = 5 * 60 * 60;
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The instant the puzzle for the given year and day of December unlocks.
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(year, 12, day);
+    UNIX_EPOCH + Duration::from_secs(days * SECS_PER_DAY + UNLOCK_OFFSET_SECS)
+}
+
+/// How long until the puzzle unlocks, or `None` if it already has.
+pub fn time_until_unlock(year: u32, day: u32) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Days between the unix epoch and a date in the proleptic Gregorian calendar, for dates on or
+/// after the epoch.
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    // Howard Hinnant's algorithm, with years starting in March so leap days fall at the end.
+    let year = if month <= 2 { year - 1 } else { year } as u64;
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month = month as u64;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of [`days_from_civil`], returning the year, month and day.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats an instant as an RFC 3339 timestamp in UTC, to the second.
+pub struct Timestamp(pub SystemTime);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self
+            .0
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (year, month, day) = civil_from_days(secs / SECS_PER_DAY);
+        let secs = secs % SECS_PER_DAY;
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        )
+    }
+}
+
+/// Formats a duration as days, hours, minutes and seconds, omitting leading zero units.
+pub struct Remaining(pub Duration);
+
+impl fmt::Display for Remaining {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (days, hours, minutes, secs) = (
+            secs / SECS_PER_DAY,
+            secs % SECS_PER_DAY / 3600,
+            secs % 3600 / 60,
+            secs % 60,
+        );
+        if days > 0 {
+            write!(f, "{days}d {hours}h {minutes}m {secs}s")
+        } else if hours > 0 {
+            write!(f, "{hours}h {minutes}m {secs}s")
+        } else if minutes > 0 {
+            write!(f, "{minutes}m {secs}s")
+        } else {
+            write!(f, "{secs}s")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{Remaining, Timestamp};
+
+    #[test]
+    fn unlock_time() {
+        let secs = |year, day| {
+            super::unlock_time(year, day)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+        assert_eq!(secs(2023, 1), 1701406800);
+        assert_eq!(secs(2015, 25), 1451019600);
+    }
+
+    #[test]
+    fn timestamp() {
+        let at = |secs| Timestamp(UNIX_EPOCH + Duration::from_secs(secs)).to_string();
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(1701406800), "2023-12-01T05:00:00Z");
+        assert_eq!(at(1709210096), "2024-02-29T12:34:56Z");
+    }
+
+    #[test]
+    fn remaining() {
+        assert_eq!(Remaining(Duration::from_secs(42)).to_string(), "42s");
+        assert_eq!(Remaining(Duration::from_secs(3661)).to_string(), "1h 1m 1s");
+        assert_eq!(
+            Remaining(Duration::from_secs(2 * 86400 + 5)).to_string(),
+            "2d 0h 0m 5s"
+        );
+    }
+}
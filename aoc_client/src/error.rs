@@ -0,0 +1,211 @@
+use std::{fmt, io, path::PathBuf, time::Duration};
+
+use reqwest::StatusCode;
+
+use crate::calendar::Remaining;
+
+/// Failures while locating or downloading an input.
+#[derive(Debug)]
+pub enum Error {
+    /// `AOC_OFFLINE` is set and the input is in none of the searched paths.
+    Offline {
+        year: u32,
+        day: u32,
+        /// Where the input can be downloaded from by hand.
+        url: String,
+        searched: Vec<PathBuf>,
+    },
+    /// An environment variable is set to a value that cannot be used.
+    InvalidSetting {
+        var: &'static str,
+        value: String,
+        /// What the value should be, such as `a number of seconds`.
+        expected: &'static str,
+    },
+    /// The profile's session variable is not set.
+    MissingSession { var: String, site: String },
+    /// The profile's session variable contains characters that cannot be sent in a cookie.
+    InvalidSession { var: String },
+    /// The site rejected the session, which usually means the cookie has expired.
+    ExpiredSession {
+        status: StatusCode,
+        var: String,
+        site: String,
+    },
+    /// The puzzle unlocks in the future, so the input was not requested.
+    Locked {
+        year: u32,
+        day: u32,
+        remaining: Duration,
+    },
+    /// The puzzle has not unlocked yet, or does not exist.
+    NotUnlocked { year: u32, day: u32, url: String },
+    /// The site responded with a status that does not have a more specific error.
+    Status { url: String, status: StatusCode },
+    /// A download looked like an error page rather than an input, so it was not cached.
+    InvalidDownload {
+        year: u32,
+        day: u32,
+        url: String,
+        reason: &'static str,
+    },
+    /// A downloaded puzzle page did not contain a puzzle description, so it was not cached.
+    InvalidPuzzle { year: u32, day: u32, url: String },
+    /// A saved input looks like an error page. Inputs in the cache are removed when this happens.
+    InvalidInput {
+        path: PathBuf,
+        reason: &'static str,
+        purged: bool,
+    },
+    /// The page returned after submitting an answer did not contain a recognized verdict.
+    UnknownResponse,
+    /// A request could not be made or its body could not be read. `request` is what was being
+    /// requested, such as `input`.
+    Http {
+        request: &'static str,
+        url: String,
+        source: reqwest::Error,
+    },
+    /// Reading, writing or locking the cache failed.
+    Cache {
+        action: &'static str,
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Error {
+    pub fn cache(action: &'static str, path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| Error::Cache {
+            action,
+            path,
+            source,
+        }
+    }
+
+    pub fn http(
+        request: &'static str,
+        url: impl Into<String>,
+    ) -> impl FnOnce(reqwest::Error) -> Self {
+        let url = url.into();
+        move |source| Error::Http {
+            request,
+            url,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Offline {
+                year,
+                day,
+                url,
+                searched,
+            } => {
+                let paths: Vec<_> = searched.iter().map(|path| path.display().to_string()).collect();
+                write!(
+                    f,
+                    "no input for {year} day {day} in {} and AOC_OFFLINE is set; save the input \
+                     from {url} to {} or unset AOC_OFFLINE",
+                    paths.join(" or "),
+                    paths.first().map_or("the inputs directory", String::as_str)
+                )
+            }
+            Error::InvalidSetting {
+                var,
+                value,
+                expected,
+            } => write!(f, "{var} is set to '{value}', but it should be {expected}"),
+            Error::MissingSession { var, site } => write!(
+                f,
+                "{var} is not set; copy the `session` cookie from {site} into {var} in the \
+                 environment or `.env`"
+            ),
+            Error::InvalidSession { var } => write!(
+                f,
+                "{var} is not a valid cookie value; it should be only the hex value of the \
+                 `session` cookie"
+            ),
+            Error::ExpiredSession { status, var, site } => write!(
+                f,
+                "{site} rejected the session ({status}); it has probably expired, so log in again \
+                 and update {var}"
+            ),
+            Error::Locked {
+                year,
+                day,
+                remaining,
+            } => write!(
+                f,
+                "the puzzle for {year} day {day} unlocks in {}; the input can be downloaded after \
+                 that",
+                Remaining(*remaining)
+            ),
+            Error::NotUnlocked { year, day, url } => write!(
+                f,
+                "{url} is not available (404); the puzzle for {year} day {day} has not unlocked \
+                 yet or does not exist"
+            ),
+            Error::Status { url, status } => write!(f, "{url} responded with {status}"),
+            Error::InvalidDownload {
+                year,
+                day,
+                url,
+                reason,
+            } => write!(
+                f,
+                "the downloaded input for {year} day {day} was not cached because {reason}; check \
+                 {url} in a browser"
+            ),
+            Error::InvalidPuzzle { year, day, url } => write!(
+                f,
+                "the downloaded page for {year} day {day} was not cached because it has no puzzle \
+                 description; check {url} in a browser"
+            ),
+            Error::InvalidInput {
+                path,
+                reason,
+                purged: true,
+            } => write!(
+                f,
+                "removed {} from the cache because {reason}; unset AOC_OFFLINE to download it again",
+                path.display()
+            ),
+            Error::InvalidInput {
+                path,
+                reason,
+                purged: false,
+            } => write!(
+                f,
+                "{} does not look like a puzzle input because {reason}; replace or delete it",
+                path.display()
+            ),
+            Error::UnknownResponse => write!(
+                f,
+                "could not find a verdict in the response to the submitted answer; check the \
+                 puzzle page in a browser"
+            ),
+            Error::Http {
+                request,
+                url,
+                source,
+            } => write!(f, "{request} request to {url} failed: {source}"),
+            Error::Cache {
+                action,
+                path,
+                source,
+            } => write!(
+                f,
+                "failed to {action} {}: {source}; check the permissions of the directory or \
+                 remove the file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,364 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use fs2::FileExt;
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::{self, HeaderMap, HeaderValue},
+    redirect::Policy,
+    StatusCode,
+};
+
+use crate::{calendar, error::Error, profile::Profile, requests, validate::validate};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Settings for requests to the site.
+pub struct AocClient {
+    /// Scheme and host the site is served from, without a trailing slash.
+    pub base_url: String,
+    /// Value of the `session` cookie, if one is configured.
+    pub session: Option<String>,
+    /// Environment variable the session comes from, for error messages.
+    pub session_var: String,
+    /// Minimum time between requests.
+    pub request_interval: Duration,
+}
+
+impl AocClient {
+    /// Reads `AOC_BASE_URL`, the profile's session and `AOC_REQUEST_INTERVAL` (in seconds) from
+    /// the environment. Fails if the interval is not a number of seconds that can be waited.
+    pub fn from_env(profile: &Profile) -> Result<Self, Error> {
+        let base_url = std::env::var("AOC_BASE_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let request_interval = match std::env::var("AOC_REQUEST_INTERVAL") {
+            Ok(secs) => request_interval(&secs).ok_or(Error::InvalidSetting {
+                var: "AOC_REQUEST_INTERVAL",
+                value: secs,
+                expected: "a number of seconds",
+            })?,
+            Err(_) => DEFAULT_REQUEST_INTERVAL,
+        };
+        Ok(AocClient {
+            base_url,
+            session: profile.session(),
+            session_var: profile.session_var(),
+            request_interval,
+        })
+    }
+
+    /// Downloads an input, waiting for the minimum interval since the last request and logging the
+    /// request in `dir`. The cache lock must be held.
+    fn input(&self, dir: &Path, year: u32, day: u32) -> Result<String, Error> {
+        let url = self.input_url(year, day);
+        self.send(dir, year, day, "input", |client| client.get(&url))
+    }
+
+    /// Where the input for a puzzle is downloaded from.
+    pub fn input_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{year}/day/{day}/input", self.base_url)
+    }
+
+    /// Builds a client that sends the session cookie and does not follow redirects.
+    fn http_client(&self, request: &'static str) -> Result<Client, Error> {
+        let session = self.session.as_ref().ok_or_else(|| Error::MissingSession {
+            var: self.session_var.clone(),
+            site: self.base_url.clone(),
+        })?;
+
+        let session = HeaderValue::from_str(&format!("session={session}")).map_err(|_| {
+            Error::InvalidSession {
+                var: self.session_var.clone(),
+            }
+        })?;
+        let content_type = HeaderValue::from_static("text/plain");
+        let user_agent = HeaderValue::from_static("jamincan/aoc2023");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, session);
+        headers.insert(header::CONTENT_TYPE, content_type);
+        headers.insert(header::USER_AGENT, user_agent);
+
+        Client::builder()
+            .default_headers(headers)
+            .redirect(Policy::none())
+            .build()
+            .map_err(Error::http(request, &self.base_url))
+    }
+
+    /// Sends the request built by `build` for a puzzle once it has unlocked, waiting for the
+    /// minimum interval since the last request and logging the request in `dir`, and returns the
+    /// body of the response. `request` is what is being requested, for error messages. The lock on
+    /// `dir` must be held.
+    pub(crate) fn send(
+        &self,
+        dir: &Path,
+        year: u32,
+        day: u32,
+        request: &'static str,
+        build: impl FnOnce(&Client) -> RequestBuilder,
+    ) -> Result<String, Error> {
+        if let Some(remaining) = calendar::time_until_unlock(year, day) {
+            return Err(Error::Locked {
+                year,
+                day,
+                remaining,
+            });
+        }
+
+        let client = self.http_client(request)?;
+        let built = build(&client)
+            .build()
+            .map_err(Error::http(request, &self.base_url))?;
+        let url = built.url().to_string();
+        requests::wait_for_turn(dir, self.request_interval)?;
+        let response = client.execute(built);
+        let outcome = match &response {
+            Ok(response) => response.status().to_string(),
+            Err(err) => format!("error: {err}"),
+        };
+        requests::log_request(dir, &url, &outcome)?;
+        let response = response.map_err(Error::http(request, &url))?;
+
+        // An invalid session is answered with a 400 or 500, or a redirect to the login page.
+        match response.status() {
+            status if status.is_success() => response.text().map_err(Error::http(request, url)),
+            StatusCode::NOT_FOUND => Err(Error::NotUnlocked { year, day, url }),
+            status
+                if status.is_redirection()
+                    || status == StatusCode::BAD_REQUEST
+                    || status == StatusCode::INTERNAL_SERVER_ERROR =>
+            {
+                Err(Error::ExpiredSession {
+                    status,
+                    var: self.session_var.clone(),
+                    site: self.base_url.clone(),
+                })
+            }
+            status => Err(Error::Status { url, status }),
+        }
+    }
+}
+
+/// Parses a request interval in seconds, which must be finite and not negative.
+fn request_interval(secs: &str) -> Option<Duration> {
+    Duration::try_from_secs_f64(secs.trim().parse().ok()?).ok()
+}
+
+/// Takes the lock on `dir`, creating it if needed, which is held until the returned file is
+/// dropped.
+pub(crate) fn lock(dir: &Path) -> Result<File, Error> {
+    fs::create_dir_all(dir).map_err(Error::cache("create", dir))?;
+    let lock_path = dir.join(".lock");
+    let file_lock = File::create(&lock_path).map_err(Error::cache("create", &lock_path))?;
+    FileExt::lock_exclusive(&file_lock).map_err(Error::cache("lock", &lock_path))?;
+    Ok(file_lock)
+}
+
+/// Ensures the input is saved at `input_path`, downloading it with `client` if it is missing.
+/// Saved inputs that fail validation are removed first. `dir` holds the lock, throttle and request
+/// log shared by everything downloading into it.
+///
+/// Returns `None` instead of downloading when `offline` is set.
+pub fn cached_input(
+    dir: &Path,
+    input_path: &Path,
+    year: u32,
+    day: u32,
+    client: &AocClient,
+    offline: bool,
+) -> Result<Option<PathBuf>, Error> {
+    let _lock = lock(dir)?;
+    let input_path = input_path.to_path_buf();
+
+    let mut purged = None;
+    if input_path.exists() {
+        let input = fs::read_to_string(&input_path).map_err(Error::cache("read", &input_path))?;
+        if let Err(reason) = validate(&input) {
+            fs::remove_file(&input_path).map_err(Error::cache("remove", &input_path))?;
+            purged = Some(reason);
+        }
+    }
+
+    if !input_path.exists() {
+        if offline {
+            return match purged {
+                Some(reason) => Err(Error::InvalidInput {
+                    path: input_path,
+                    reason,
+                    purged: true,
+                }),
+                None => Ok(None),
+            };
+        }
+        let aoc_input = client.input(dir, year, day)?;
+        validate(&aoc_input).map_err(|reason| Error::InvalidDownload {
+            year,
+            day,
+            url: client.input_url(year, day),
+            reason,
+        })?;
+        if let Some(parent) = input_path.parent() {
+            fs::create_dir_all(parent).map_err(Error::cache("create", parent))?;
+        }
+        fs::write(&input_path, aoc_input).map_err(Error::cache("write", &input_path))?;
+    }
+
+    Ok(Some(input_path))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::{Path, PathBuf},
+        thread,
+        time::Duration,
+    };
+
+    use super::{cached_input, request_interval, AocClient};
+    use crate::{
+        error::Error,
+        mock::{client, response, serve},
+    };
+
+    /// Fetches the input for 2023 day 5 into `dir`.
+    fn cached(dir: &Path, client: &AocClient, offline: bool) -> Result<Option<PathBuf>, Error> {
+        cached_input(dir, &dir.join("y2023d5.txt"), 2023, 5, client, offline)
+    }
+
+    #[test]
+    fn downloads_and_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let (base_url, server) = serve(&[response("200 OK", "", "1 2 3\n")]);
+
+        let path = cached(dir, &client(&base_url), false).unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "1 2 3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/5/input "));
+        assert!(requests[0].contains("cookie: session=abc123"));
+        let log = std::fs::read_to_string(dir.join("requests.log")).unwrap();
+        assert!(log.contains(&format!("{base_url}/2023/day/5/input\t200 OK")));
+    }
+
+    #[test]
+    fn not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let (base_url, server) = serve(&[response("404 Not Found", "", "404 Not Found\n")]);
+
+        let result = cached(dir, &client(&base_url), false);
+        assert!(matches!(
+            result,
+            Err(Error::NotUnlocked {
+                year: 2023,
+                day: 5,
+                ..
+            })
+        ));
+        let message = result.unwrap_err().to_string();
+        assert!(message.starts_with(&format!("{base_url}/2023/day/5/input is not available")));
+        assert!(!dir.join("y2023d5.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn redirect_to_login() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let (base_url, server) = serve(&[response("302 Found", "Location: /auth/login\r\n", "")]);
+
+        let result = cached(dir, &client(&base_url), false);
+        assert!(matches!(result, Err(Error::ExpiredSession { .. })));
+        let message = result.unwrap_err().to_string();
+        assert!(message.starts_with(&format!("{base_url} rejected the session")));
+        assert!(!dir.join("y2023d5.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn error_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let (base_url, server) = serve(&[response(
+            "200 OK",
+            "",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+
+        let result = cached(dir, &client(&base_url), false);
+        assert!(matches!(result, Err(Error::InvalidDownload { .. })));
+        assert!(!dir.join("y2023d5.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn purges_invalid_cache_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        std::fs::write(dir.join("y2023d5.txt"), "<!DOCTYPE html>\n<html></html>").unwrap();
+        let (base_url, server) = serve(&[response("200 OK", "", "1 2 3\n")]);
+
+        let result = cached(dir, &client(&base_url), true);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidInput { purged: true, .. })
+        ));
+        assert!(!dir.join("y2023d5.txt").exists());
+
+        let path = cached(dir, &client(&base_url), false).unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "1 2 3\n");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn connection_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let result = cached(dir, &client("http://127.0.0.1:1"), false);
+        let message = result.unwrap_err().to_string();
+        assert!(message.starts_with("input request to http://127.0.0.1:1/2023/day/5/input failed"));
+    }
+
+    #[test]
+    fn offline_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let result = cached(dir, &client("http://127.0.0.1:1"), true);
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn request_intervals() {
+        assert_eq!(request_interval("2.5"), Some(Duration::from_millis(2500)));
+        assert_eq!(request_interval("0"), Some(Duration::ZERO));
+        for invalid in ["-1", "NaN", "inf", "five", ""] {
+            assert_eq!(request_interval(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn parallel_builds_download_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let (base_url, server) = serve(&[response("200 OK", "", "1 2 3\n")]);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let dir = dir.to_path_buf();
+                let client = client(&base_url);
+                thread::spawn(move || cached(&dir, &client, false))
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().unwrap().is_some());
+        }
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+}
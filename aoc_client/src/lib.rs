@@ -0,0 +1,75 @@
+//! Downloading and caching Advent of Code puzzle inputs and descriptions, shared by `include_aoc!`
+//! and the runner.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub use error::Error;
+pub use fetch::AocClient;
+pub use profile::{inputs_dir, Profile};
+use validate::validate;
+
+pub mod answers;
+pub mod calendar;
+mod error;
+pub mod fetch;
+pub mod lines;
+#[cfg(test)]
+mod mock;
+mod profile;
+pub mod puzzle;
+mod requests;
+pub mod submit;
+pub mod validate;
+
+/// Whether `AOC_OFFLINE` is set, which disables downloading.
+pub fn offline() -> bool {
+    flag("AOC_OFFLINE")
+}
+
+/// Whether `AOC_REFRESH` is set, which downloads puzzle pages that do not show part two yet again.
+pub fn refresh() -> bool {
+    flag("AOC_REFRESH")
+}
+
+fn flag(var: &str) -> bool {
+    std::env::var(var).is_ok_and(|val| !matches!(val.as_str(), "" | "0" | "false"))
+}
+
+/// Returns the path of an input, preferring `project_path` and otherwise ensuring it is saved at
+/// `cache_path`, downloading it with the profile's session if needed. See
+/// [`fetch::cached_input`] for the role of `cache_dir`.
+pub fn locate_input(
+    project_path: &Path,
+    cache_dir: &Path,
+    cache_path: &Path,
+    profile: &Profile,
+    year: u32,
+    day: u32,
+) -> Result<PathBuf, Error> {
+    if project_path.exists() {
+        let input = fs::read_to_string(project_path).map_err(Error::cache("read", project_path))?;
+        validate(&input).map_err(|reason| Error::InvalidInput {
+            path: project_path.to_path_buf(),
+            reason,
+            purged: false,
+        })?;
+        return Ok(project_path.to_path_buf());
+    }
+
+    let client = AocClient::from_env(profile)?;
+    fetch::cached_input(cache_dir, cache_path, year, day, &client, offline())?.ok_or_else(|| {
+        let mut searched = vec![project_path.to_path_buf()];
+        if cache_path != project_path {
+            searched.push(cache_path.to_path_buf());
+        }
+        Error::Offline {
+            year,
+            day,
+            url: client.input_url(year, day),
+            searched,
+        }
+    })
+}
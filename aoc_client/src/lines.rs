@@ -0,0 +1,77 @@
+//! Files that hold one record per line and are only ever appended to, such as stored answers,
+//! submissions and timings.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+};
+
+use crate::error::Error;
+
+/// Reads the records in the file at `path`, which need not exist yet, parsing each line with
+/// `parse`. Lines starting with `#` and lines that `parse` rejects are skipped, so the file can be
+/// annotated by hand.
+pub fn load<T>(path: &Path, parse: impl FnMut(&str) -> Option<T>) -> Result<Vec<T>, Error> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .filter_map(parse)
+            .collect()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(Error::cache("read", path)(err)),
+    }
+}
+
+/// Appends `lines`, each ending in a newline, to the file at `path`, creating it and its directory
+/// if needed, with `header` first if the file is empty. Everything already in the file is left as
+/// it is.
+pub fn append(path: &Path, header: &str, lines: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(Error::cache("create", parent))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(Error::cache("open", path))?;
+    let existing = fs::read(path).map_err(Error::cache("read", path))?;
+    let mut contents = String::new();
+    match existing.last() {
+        None => contents.push_str(header),
+        // Start a new line if the file was last edited by hand without a final newline.
+        Some(&last) if last != b'\n' => contents.push('\n'),
+        Some(_) => {}
+    }
+    contents.push_str(lines);
+    file.write_all(contents.as_bytes())
+        .map_err(Error::cache("write", path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{append, load};
+
+    #[test]
+    fn append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/numbers.tsv");
+        let parse = |line: &str| line.parse::<u32>().ok();
+        assert_eq!(load(&path, parse).unwrap(), []);
+
+        append(&path, "# numbers\n", "1\n2\n").unwrap();
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "# note\nthree",
+        )
+        .unwrap();
+        append(&path, "# numbers\n", "4\n").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# numbers\n1\n2\n# note\nthree\n4\n"
+        );
+        assert_eq!(load(&path, parse).unwrap(), [1, 2, 4]);
+    }
+}
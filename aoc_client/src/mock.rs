@@ -0,0 +1,61 @@
+//! A stand-in for the site that serves canned responses, for tests.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::fetch::AocClient;
+
+/// Serves each of `responses` to one connection in turn, returning the requests received.
+pub fn serve(responses: &[&'static str]) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let responses = responses.to_vec();
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            requests.push(request);
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+        requests
+    });
+    (base_url, handle)
+}
+
+pub fn response(status: &str, headers: &str, body: &str) -> &'static str {
+    format!(
+        "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .leak()
+}
+
+pub fn client(base_url: &str) -> AocClient {
+    AocClient {
+        base_url: base_url.to_string(),
+        session: Some("abc123".to_string()),
+        session_var: "AOC_SESSION".to_string(),
+        request_interval: Duration::ZERO,
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// An account whose session and inputs are kept separate from other accounts.
+///
+/// The default profile uses `AOC_SESSION`, and a profile named `alice` uses `AOC_SESSION_ALICE`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Profile(Option<String>);
+
+impl Profile {
+    /// A named profile. The name `default` refers to the default profile.
+    pub fn named(name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty() || name == "default" {
+            Profile(None)
+        } else {
+            Profile(Some(name))
+        }
+    }
+
+    /// The profile selected by `AOC_PROFILE`, or the default profile if it is not set.
+    pub fn from_env() -> Self {
+        std::env::var("AOC_PROFILE")
+            .map(|name| Profile::named(&name))
+            .unwrap_or_default()
+    }
+
+    /// Every profile that has a session configured in the environment.
+    pub fn all() -> Vec<Self> {
+        let mut profiles: Vec<_> = std::env::vars()
+            .filter_map(|(var, _)| {
+                if var == "AOC_SESSION" {
+                    Some(Profile(None))
+                } else {
+                    var.strip_prefix("AOC_SESSION_").map(Profile::named)
+                }
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.0.cmp(&b.0));
+        profiles.dedup();
+        profiles
+    }
+
+    pub fn name(&self) -> &str {
+        self.0.as_deref().unwrap_or("default")
+    }
+
+    /// The environment variable holding this profile's session cookie.
+    pub fn session_var(&self) -> String {
+        match &self.0 {
+            Some(name) => format!("AOC_SESSION_{}", name.to_ascii_uppercase()),
+            None => "AOC_SESSION".to_string(),
+        }
+    }
+
+    /// This profile's session cookie, if it is set.
+    pub fn session(&self) -> Option<String> {
+        std::env::var(self.session_var()).ok()
+    }
+
+    /// This profile's directory for a year in the project directory: `{inputs}/2023` for the
+    /// default profile and `{inputs}/{name}/2023` for the others.
+    pub fn project_dir(&self, inputs: &Path, year: u32) -> PathBuf {
+        let dir = match &self.0 {
+            Some(name) => inputs.join(name),
+            None => inputs.to_path_buf(),
+        };
+        dir.join(year.to_string())
+    }
+
+    /// Location of an input in the project directory, `day05.txt` in [`Profile::project_dir`].
+    pub fn project_input_path(&self, inputs: &Path, year: u32, day: u32) -> PathBuf {
+        self.project_dir(inputs, year)
+            .join(format!("day{day:02}.txt"))
+    }
+
+    /// Location of a puzzle page in the project directory, `day05.html` in
+    /// [`Profile::project_dir`].
+    pub fn project_puzzle_path(&self, inputs: &Path, year: u32, day: u32) -> PathBuf {
+        self.project_dir(inputs, year)
+            .join(format!("day{day:02}.html"))
+    }
+
+    /// Name of an input in the build cache.
+    pub fn cache_file_name(&self, year: u32, day: u32) -> String {
+        match &self.0 {
+            Some(name) => format!("y{year}d{day}-{name}.txt"),
+            None => format!("y{year}d{day}.txt"),
+        }
+    }
+
+    /// Name of a puzzle page in the build cache. Pages differ by profile because part two is only
+    /// shown to accounts that have solved part one.
+    pub fn puzzle_file_name(&self, year: u32, day: u32) -> String {
+        match &self.0 {
+            Some(name) => format!("y{year}d{day}-{name}.html"),
+            None => format!("y{year}d{day}.html"),
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The project inputs directory: `inputs` in `manifest_dir` unless overridden by `AOC_INPUTS`.
+/// Relative overrides are resolved against `manifest_dir`.
+pub fn inputs_dir(manifest_dir: &Path) -> PathBuf {
+    let dir = std::env::var_os("AOC_INPUTS").unwrap_or_else(|| "inputs".into());
+    manifest_dir.join(dir)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::Profile;
+
+    #[test]
+    fn paths() {
+        let inputs = Path::new("inputs");
+        let default = Profile::named("default");
+        assert_eq!(default, Profile::default());
+        assert_eq!(default.session_var(), "AOC_SESSION");
+        assert_eq!(
+            default.project_input_path(inputs, 2023, 5),
+            Path::new("inputs/2023/day05.txt")
+        );
+        assert_eq!(default.cache_file_name(2023, 5), "y2023d5.txt");
+
+        let alice = Profile::named("Alice");
+        assert_eq!(alice.name(), "alice");
+        assert_eq!(alice.session_var(), "AOC_SESSION_ALICE");
+        assert_eq!(
+            alice.project_input_path(inputs, 2023, 5),
+            Path::new("inputs/alice/2023/day05.txt")
+        );
+        assert_eq!(
+            alice.project_puzzle_path(inputs, 2023, 5),
+            Path::new("inputs/alice/2023/day05.html")
+        );
+        assert_eq!(alice.cache_file_name(2023, 5), "y2023d5-alice.txt");
+        assert_eq!(alice.puzzle_file_name(2023, 5), "y2023d5-alice.html");
+    }
+}
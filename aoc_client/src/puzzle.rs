@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::Error,
+    fetch::{self, AocClient},
+};
+
+const ARTICLE_START: &str = "<article class=\"day-desc\">";
+const ARTICLE_END: &str = "</article>";
+
+/// The examples and example answers in a puzzle description.
+///
+/// Part two is only shown once part one has been solved, so until then its examples and answer are
+/// missing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// Every `<pre><code>` block in the order it appears, with markup removed.
+    pub examples: Vec<String>,
+    /// For each part shown, the last emphasized code in its description, which is the answer to
+    /// its example.
+    pub answers: Vec<Option<String>>,
+}
+
+impl Puzzle {
+    /// Reads the examples and answers from a puzzle page.
+    pub fn parse(page: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        for article in sections(page, ARTICLE_START, ARTICLE_END) {
+            puzzle
+                .examples
+                .extend(sections(article, "<pre><code>", "</code></pre>").map(text));
+            let answer = sections(article, "<code><em>", "</em></code>")
+                .chain(sections(article, "<em><code>", "</code></em>"))
+                // Both kinds of section borrow from the article, so the last one starts last.
+                .max_by_key(|answer| answer.as_ptr())
+                .map(text);
+            puzzle.answers.push(answer);
+        }
+        puzzle
+    }
+
+    /// Whether part two has been revealed.
+    pub fn has_part2(&self) -> bool {
+        self.answers.len() > 1
+    }
+}
+
+/// The text between each `start` and the following `end`.
+fn sections<'a>(text: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    text.split(start)
+        .skip(1)
+        .filter_map(move |section| section.split_once(end).map(|(inside, _)| inside))
+}
+
+/// Removes tags from HTML and decodes the entities the site uses.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].split_once('>').map_or("", |(_, rest)| rest);
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+impl AocClient {
+    /// Downloads a puzzle page, waiting for the minimum interval since the last request and logging
+    /// the request in `dir`. The cache lock must be held.
+    fn puzzle(&self, dir: &Path, year: u32, day: u32) -> Result<String, Error> {
+        let url = self.puzzle_url(year, day);
+        self.send(dir, year, day, "puzzle page", |client| client.get(&url))
+    }
+
+    /// Where the page describing a puzzle is.
+    pub fn puzzle_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{year}/day/{day}", self.base_url)
+    }
+}
+
+/// Ensures the puzzle page is saved at `page_path`, downloading it with `client` if it is missing
+/// or `refresh` is set. See [`fetch::cached_input`] for the role of `dir` and `offline`.
+pub fn cached_puzzle(
+    dir: &Path,
+    page_path: &Path,
+    year: u32,
+    day: u32,
+    client: &AocClient,
+    offline: bool,
+    refresh: bool,
+) -> Result<Option<PathBuf>, Error> {
+    let _lock = fetch::lock(dir)?;
+    let page_path = page_path.to_path_buf();
+
+    if !page_path.exists() || (refresh && !offline) {
+        if offline {
+            return Ok(None);
+        }
+        let page = client.puzzle(dir, year, day)?;
+        if !page.contains(ARTICLE_START) {
+            return Err(Error::InvalidPuzzle {
+                year,
+                day,
+                url: client.puzzle_url(year, day),
+            });
+        }
+        if let Some(parent) = page_path.parent() {
+            fs::create_dir_all(parent).map_err(Error::cache("create", parent))?;
+        }
+        fs::write(&page_path, page).map_err(Error::cache("write", &page_path))?;
+    }
+
+    Ok(Some(page_path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cached_puzzle, Puzzle};
+    use crate::{
+        error::Error,
+        mock::{client, response, serve},
+    };
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Example ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+a&lt;b&gt;&amp;c
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54338</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code><em>two</em>1nine
+</code></pre>
+<p>The <code><em>1</em></code> is ignored, giving <em><code>281</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn parse() {
+        let puzzle = Puzzle::parse(PAGE);
+        assert_eq!(puzzle.examples, ["1abc2\na<b>&c\n", "two1nine\n"]);
+        assert_eq!(
+            puzzle.answers,
+            [Some("142".to_string()), Some("281".to_string())]
+        );
+        assert!(puzzle.has_part2());
+
+        let (part1, _) = PAGE.split_once("<p>Your puzzle").unwrap();
+        let puzzle = Puzzle::parse(part1);
+        assert_eq!(puzzle.examples.len(), 1);
+        assert!(!puzzle.has_part2());
+    }
+
+    #[test]
+    fn downloads_and_refreshes() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let path = dir.join("y2023d1.html");
+        let (base_url, server) = serve(&[
+            response("200 OK", "", PAGE),
+            response("200 OK", "", "<html>Please log in</html>"),
+        ]);
+        let client = client(&base_url);
+
+        let cached = cached_puzzle(dir, &path, 2023, 1, &client, false, false);
+        assert_eq!(cached.unwrap(), Some(path.clone()));
+        let cached = cached_puzzle(dir, &path, 2023, 1, &client, false, false);
+        assert_eq!(cached.unwrap(), Some(path.clone()));
+        let cached = cached_puzzle(dir, &path, 2023, 1, &client, true, true);
+        assert_eq!(cached.unwrap(), Some(path.clone()));
+
+        let refreshed = cached_puzzle(dir, &path, 2023, 1, &client, false, true);
+        assert!(matches!(
+            refreshed,
+            Err(Error::InvalidPuzzle {
+                year: 2023,
+                day: 1,
+                ..
+            })
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), PAGE);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("GET /2023/day/1 "));
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{calendar::Timestamp, error::Error};
+
+/// Time of the most recent request, in milliseconds since the unix epoch.
+const LAST_REQUEST_FILE: &str = ".last_request";
+/// Append-only record of every request made.
+const LOG_FILE: &str = "requests.log";
+
+/// Blocks until `min_interval` has passed since the last request made from `dir`, then
+/// records that a request is being made now.
+///
+/// The caller must hold the cache lock so that parallel builds take turns.
+pub fn wait_for_turn(dir: &Path, min_interval: Duration) -> Result<(), Error> {
+    let path = dir.join(LAST_REQUEST_FILE);
+    let last_request = fs::read_to_string(&path)
+        .ok()
+        .and_then(|millis| millis.trim().parse().ok())
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+    if let Some(last_request) = last_request {
+        let elapsed = SystemTime::now()
+            .duration_since(last_request)
+            .unwrap_or_default();
+        if let Some(wait) = min_interval.checked_sub(elapsed) {
+            thread::sleep(wait);
+        }
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    fs::write(&path, now.to_string()).map_err(Error::cache("write", &path))
+}
+
+/// Appends a line with the current time, the url and the outcome of a request to the log in `dir`.
+pub fn log_request(dir: &Path, url: &str, outcome: &str) -> Result<(), Error> {
+    let path = dir.join(LOG_FILE);
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(Error::cache("open", &path))?;
+    writeln!(log, "{}\t{url}\t{outcome}", Timestamp(SystemTime::now()))
+        .map_err(Error::cache("write", &path))
+}
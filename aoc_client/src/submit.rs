@@ -0,0 +1,297 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::SystemTime,
+};
+
+use crate::{
+    calendar::Timestamp,
+    error::Error,
+    fetch::{self, AocClient},
+    lines,
+};
+
+/// The site's response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Wrong,
+    /// An answer was submitted too recently, with the time left to wait as the site states it.
+    Wait(String),
+    /// The part has already been solved, or part 1 has not been solved yet.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict from the page returned after submitting an answer.
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Verdict::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("a while", |(wait, _)| wait);
+            Some(Verdict::Wait(wait.to_string()))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the site rejected the answer itself, as opposed to not judging it.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::TooHigh => f.write_str("too high"),
+            Verdict::TooLow => f.write_str("too low"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::Wait(wait) => write!(f, "wait {wait}"),
+            Verdict::AlreadySolved => f.write_str("already solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Verdict::Correct,
+            "too high" => Verdict::TooHigh,
+            "too low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "already solved" => Verdict::AlreadySolved,
+            _ => match s.strip_prefix("wait ") {
+                Some(wait) => Verdict::Wait(wait.to_string()),
+                None => return Err(format!("unknown verdict '{s}'")),
+            },
+        })
+    }
+}
+
+impl AocClient {
+    /// Submits an answer for a part, logging the request in `dir`.
+    pub fn submit(
+        &self,
+        dir: &Path,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, Error> {
+        let _lock = fetch::lock(dir)?;
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let page = self.send(dir, year, day, "answer", |client| {
+            client
+                .post(&url)
+                .form(&[("level", level.as_str()), ("answer", answer)])
+        })?;
+        Verdict::parse(&page).ok_or(Error::UnknownResponse)
+    }
+}
+
+/// One submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer submitted for a year, stored as [`lines`] of tab-separated time, day, part, answer
+/// and verdict.
+pub struct Submissions {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Submissions {
+    /// Loads the submissions stored at `path`, which need not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let attempts = lines::load(&path, parse_attempt)?;
+        Ok(Submissions { path, attempts })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Checks whether submitting `answer` could possibly be useful, returning why not otherwise.
+    pub fn check(&self, day: u32, part: u8, answer: &str) -> Result<(), String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+        let numeric = answer.parse::<i128>().ok();
+        for attempt in attempts {
+            if attempt.verdict == Verdict::Correct {
+                return Err(format!("already solved with {}", attempt.answer));
+            }
+            if attempt.answer == answer && attempt.verdict.is_wrong() {
+                return Err(format!(
+                    "{answer} was already submitted and was {}",
+                    attempt.verdict
+                ));
+            }
+            let (Some(answer), Ok(previous)) = (numeric, attempt.answer.parse::<i128>()) else {
+                continue;
+            };
+            match attempt.verdict {
+                Verdict::TooHigh if answer >= previous => {
+                    return Err(format!("{previous} was too high"))
+                }
+                Verdict::TooLow if answer <= previous => {
+                    return Err(format!("{previous} was too low"))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends an attempt to the stored submissions.
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Error> {
+        let line = format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            Timestamp(SystemTime::now()),
+            attempt.day,
+            attempt.part,
+            attempt.answer,
+            attempt.verdict
+        );
+        lines::append(&self.path, "", &line)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.split('\t');
+    let _time = fields.next()?;
+    Some(Attempt {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+        verdict: fields.next()?.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Attempt, Submissions, Verdict};
+    use crate::mock::{client, response, serve};
+
+    #[test]
+    fn parse_verdict() {
+        let page = |text| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            )),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.")),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data.")),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::parse(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a>")),
+            Some(Verdict::Wait("4m 32s".to_string()))
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(Verdict::parse(&page("Something else")), None);
+    }
+
+    #[test]
+    fn submit() {
+        let dir = tempfile::tempdir().unwrap();
+        let (base_url, server) = serve(&[response(
+            "200 OK",
+            "",
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+
+        let verdict = client(&base_url)
+            .submit(dir.path(), 2023, 5, 1, "35")
+            .unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/5/answer "));
+        assert!(requests[0].ends_with("level=1&answer=35"));
+    }
+
+    #[test]
+    fn check() {
+        let attempt = |answer: &str, verdict| Attempt {
+            day: 5,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+        };
+        let submissions = Submissions {
+            path: "submissions.tsv".into(),
+            attempts: vec![
+                attempt("100", Verdict::TooHigh),
+                attempt("10", Verdict::TooLow),
+                attempt("50", Verdict::Wrong),
+                attempt("40", Verdict::Wait("1m".to_string())),
+            ],
+        };
+        assert!(submissions.check(5, 1, "100").is_err());
+        assert!(submissions.check(5, 1, "150").is_err());
+        assert!(submissions.check(5, 1, "5").is_err());
+        assert!(submissions.check(5, 1, "50").is_err());
+        assert!(submissions.check(5, 1, "40").is_ok());
+        assert!(submissions.check(5, 1, "60").is_ok());
+        assert!(submissions.check(5, 2, "100").is_ok());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.tsv");
+
+        let mut submissions = Submissions::load(&path).unwrap();
+        let attempt = Attempt {
+            day: 5,
+            part: 2,
+            answer: "46".to_string(),
+            verdict: Verdict::Wait("30s".to_string()),
+        };
+        submissions.record(attempt.clone()).unwrap();
+        assert_eq!(Submissions::load(&path).unwrap().attempts(), &[attempt]);
+    }
+}
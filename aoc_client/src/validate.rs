@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+/// Messages the site serves in place of an input, with a description of what they mean.
+const ERROR_PAGES: &[(&str, &str)] = &[
+    (
+        "Please don't repeatedly request this endpoint before it unlocks!",
+        "the puzzle had not unlocked yet",
+    ),
+    (
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        "the session was not accepted",
+    ),
+    ("404 Not Found", "the puzzle was not found"),
+    (
+        "500 Internal Server Error",
+        "the site returned a server error",
+    ),
+];
+
+/// Checks that text looks like a puzzle input rather than an error page, returning the reason it
+/// was rejected otherwise.
+pub fn validate(input: &str) -> Result<(), &'static str> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("it is empty");
+    }
+    if let Some((_, reason)) = ERROR_PAGES
+        .iter()
+        .find(|(pattern, _)| trimmed.starts_with(pattern))
+    {
+        return Err(reason);
+    }
+    let lowercase = trimmed.to_ascii_lowercase();
+    if lowercase.starts_with("<!doctype html") || lowercase.starts_with("<html") {
+        return Err("it is an HTML page");
+    }
+    Ok(())
+}
+
+/// Finds the saved inputs (`.txt` files) anywhere under `dir` that fail [`validate`], with the
+/// reason each was rejected, in path order. A missing directory has none.
+pub fn invalid_inputs(dir: &Path) -> Result<Vec<(PathBuf, &'static str)>, Error> {
+    let mut invalid = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(Error::cache("read", &dir)(err)),
+        };
+        for entry in entries {
+            let path = entry.map_err(Error::cache("read", &dir))?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "txt") {
+                let input = fs::read_to_string(&path).map_err(Error::cache("read", &path))?;
+                if let Err(reason) = validate(&input) {
+                    invalid.push((path, reason));
+                }
+            }
+        }
+    }
+    invalid.sort();
+    Ok(invalid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{invalid_inputs, validate};
+
+    #[test]
+    fn accepts_input() {
+        assert!(validate("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n").is_ok());
+        assert!(validate("<>^v\n").is_ok());
+    }
+
+    #[test]
+    fn rejects_error_pages() {
+        assert!(validate("").is_err());
+        assert!(validate(" \n").is_err());
+        assert!(validate("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n").is_err());
+        assert!(validate(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_err());
+        assert!(validate("<!DOCTYPE html>\n<html lang=\"en-us\">\n</html>").is_err());
+    }
+
+    #[test]
+    fn finds_invalid_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        std::fs::create_dir_all(dir.join("alice/2023")).unwrap();
+        std::fs::write(dir.join("y2023d5.txt"), "1 2 3\n").unwrap();
+        std::fs::write(dir.join("y2023d6.txt"), "404 Not Found\n").unwrap();
+        std::fs::write(dir.join("y2023d6.html"), "<html></html>").unwrap();
+        std::fs::write(dir.join("alice/2023/day07.txt"), "").unwrap();
+
+        assert_eq!(
+            invalid_inputs(dir).unwrap(),
+            [
+                (dir.join("alice/2023/day07.txt"), "it is empty"),
+                (dir.join("y2023d6.txt"), "the puzzle was not found"),
+            ]
+        );
+        assert_eq!(invalid_inputs(&dir.join("missing")).unwrap(), []);
+    }
+}
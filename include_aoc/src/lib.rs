@@ -1,12 +1,11 @@
-use std::fs::{self, File};
-
-use fs2::FileExt;
-use proc_macro::{TokenStream, TokenTree};
-use reqwest::{
-    blocking::Client,
-    header::{self, HeaderMap, HeaderValue},
-    redirect::Policy,
+use std::path::{Path, PathBuf};
+
+use aoc_client::{
+    calendar,
+    puzzle::{self, Puzzle},
+    AocClient, Error, Profile,
 };
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
 use syn::parse_macro_input;
 
 struct Params {
@@ -16,69 +15,309 @@ struct Params {
 
 impl syn::parse::Parse for Params {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        use syn::{punctuated::Punctuated, LitInt, Token};
-
-        let literals = Punctuated::<LitInt, Token![,]>::parse_terminated(input)?;
-        if literals.len() < 2 {
-            return Err(syn::Error::new(input.span(), "year and day required"));
-        } else if literals.len() > 2 {
-            return Err(syn::Error::new(input.span(), "only year and day allowed"));
-        }
-        let year = literals[0].base10_parse()?;
-        let day = literals[1].base10_parse()?;
+        let literals = parse_literals(input, 2, "year and day")?;
+        let (year, day) = year_day(&literals)?;
         Ok(Params { year, day })
     }
 }
 
+/// Parameters of the example macros: a year, a day and an index or part.
+struct ExampleParams {
+    year: u32,
+    day: u32,
+    index: usize,
+}
+
+impl syn::parse::Parse for ExampleParams {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let literals = parse_literals(input, 3, "year, day and index")?;
+        let (year, day) = year_day(&literals)?;
+        let index = literals[2].base10_parse()?;
+        Ok(ExampleParams { year, day, index })
+    }
+}
+
+/// Parses exactly `count` comma-separated integers, described by `names` in errors.
+fn parse_literals(
+    input: syn::parse::ParseStream,
+    count: usize,
+    names: &str,
+) -> syn::Result<Vec<syn::LitInt>> {
+    use syn::{punctuated::Punctuated, LitInt, Token};
+
+    let literals = Punctuated::<LitInt, Token![,]>::parse_terminated(input)?;
+    if literals.len() < count {
+        return Err(syn::Error::new(input.span(), format!("{names} required")));
+    } else if literals.len() > count {
+        return Err(syn::Error::new(
+            input.span(),
+            format!("only {names} allowed"),
+        ));
+    }
+    Ok(literals.into_iter().collect())
+}
+
+/// Parses and checks the year and day in the first two literals.
+fn year_day(literals: &[syn::LitInt]) -> syn::Result<(u32, u32)> {
+    let year = literals[0].base10_parse()?;
+    if year < calendar::FIRST_YEAR {
+        return Err(syn::Error::new(
+            literals[0].span(),
+            format!("Advent of Code started in {}", calendar::FIRST_YEAR),
+        ));
+    }
+    let day = literals[1].base10_parse()?;
+    if !(1..=calendar::LAST_DAY).contains(&day) {
+        return Err(syn::Error::new(
+            literals[1].span(),
+            format!("day must be between 1 and {}", calendar::LAST_DAY),
+        ));
+    }
+    Ok((year, day))
+}
+
+/// Expands to the puzzle input for the given year and day as a `&'static str`.
+///
+/// Inputs are looked up in the project input directory first (`inputs/{year}/day{day:02}.txt`
+/// relative to the calling crate, or the directory named by `AOC_INPUTS`), then in the build cache,
+/// and are downloaded into the build cache if neither has them. Setting `AOC_OFFLINE` (in the
+/// environment or `.env`) disables downloading entirely, so a missing input becomes a compile error
+/// instead.
+///
+/// `AOC_PROFILE` selects which account's input to use. A profile named `alice` downloads with
+/// `AOC_SESSION_ALICE` and reads `inputs/alice/{year}/day{day:02}.txt`; without it, `AOC_SESSION`
+/// and the paths above are used.
+///
+/// Downloads come from `AOC_BASE_URL` (default `https://adventofcode.com`), which can point at a
+/// local server for testing. They are spaced at least `AOC_REQUEST_INTERVAL` seconds (default 5)
+/// apart, even across parallel builds, and every request is logged to `requests.log` in the build
+/// cache.
+///
+/// Downloads that look like an error page rather than an input are not cached, and cached inputs
+/// that look like one are removed from the cache and downloaded again.
 #[proc_macro]
 pub fn include_aoc(input: TokenStream) -> TokenStream {
-    let Params { year, day } = parse_macro_input!(input as Params);
+    let params = parse_macro_input!(input as Params);
+    match expand(params) {
+        Ok(tokens) => tokens,
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Expands to the `index`th example in the puzzle description for the given year and day, counting
+/// from 0, as a `&'static str`.
+///
+/// Examples are the `<pre><code>` blocks of the description with their markup removed. Puzzle pages
+/// saved in the project input directory (`inputs/{year}/day{day:02}.html`, next to the inputs) are
+/// used first, so checked-in pages work with `AOC_OFFLINE`. Otherwise pages are downloaded into the
+/// build cache with the same settings as `include_aoc!`, and the calling crate is rebuilt when the
+/// page changes. Examples in part two only appear once part one is solved, but a cached page is not
+/// downloaded again on every build: if one is missing, build with `AOC_REFRESH` set (in the
+/// environment or `.env`) to download the page again.
+///
+/// ```ignore
+/// const EXAMPLE: &str = include_aoc_example!(2023, 5, 0);
+/// ```
+#[proc_macro]
+pub fn include_aoc_example(input: TokenStream) -> TokenStream {
+    let ExampleParams { year, day, index } = parse_macro_input!(input as ExampleParams);
+    let example = puzzle_item(year, day, &format!("example {index}"), |puzzle| {
+        puzzle.examples.get(index).cloned()
+    });
+    match example {
+        Ok((page, example)) => tracked(&page, Literal::string(&example)),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Expands to the answer to the example for a part (1 or 2) of the puzzle for the given year and
+/// day: the last emphasized code in that part's description.
+///
+/// Integer answers expand to an unsuffixed integer literal so they compare against any integer
+/// type, and other answers expand to a `&'static str`. Pages are downloaded as for
+/// `include_aoc_example!`.
+///
+/// ```ignore
+/// assert_eq!(pt1(include_aoc_example!(2023, 5, 0)).unwrap(), include_aoc_answer!(2023, 5, 1));
+/// ```
+#[proc_macro]
+pub fn include_aoc_answer(input: TokenStream) -> TokenStream {
+    let ExampleParams {
+        year,
+        day,
+        index: part,
+    } = parse_macro_input!(input as ExampleParams);
+    let what = format!("example answer for part {part}");
+    let answer = puzzle_item(year, day, &what, |puzzle| {
+        puzzle.answers.get(part.checked_sub(1)?).cloned().flatten()
+    });
+    match answer {
+        Ok((page, answer)) => tracked(
+            &page,
+            match answer.parse::<i128>() {
+                Ok(answer) => Literal::i128_unsuffixed(answer),
+                Err(_) => Literal::string(&answer),
+            },
+        ),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Expands to the build cache that the other macros download into, as a `&'static str`, so that
+/// tools downloading at run time share its lock, request throttle and request log.
+///
+/// ```ignore
+/// let cache = Path::new(include_aoc::cache_dir!());
+/// ```
+#[proc_macro]
+pub fn cache_dir(input: TokenStream) -> TokenStream {
+    if let Some(token) = input.into_iter().next() {
+        return syn::Error::new(token.span().into(), "cache_dir! takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    let dir = scratch::path("include_aoc");
+    TokenTree::Literal(Literal::string(&dir.to_string_lossy())).into()
+}
+
+fn compile_error(message: impl std::fmt::Display) -> TokenStream {
+    syn::Error::new(Span::call_site().into(), message)
+        .into_compile_error()
+        .into()
+}
+
+fn expand(Params { year, day }: Params) -> Result<TokenStream, Error> {
+    let _ = dotenv::dotenv();
 
+    let profile = Profile::from_env();
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let project_path =
+        profile.project_input_path(&aoc_client::inputs_dir(Path::new(&manifest_dir)), year, day);
     let dir = scratch::path("include_aoc");
-    let file_lock = File::create(dir.join(".lock")).expect("failed to create lock file in cache");
-    FileExt::lock_exclusive(&file_lock).expect("failed to get lock on cache");
-    let file_name = format!("y{year}d{day}.txt");
-    let input_path = dir.join(file_name);
-
-    let aoc_input = if !input_path.exists() {
-        let aoc_input = load_from_aoc(year, day);
-        fs::write(input_path, &aoc_input).expect("unable to save puzzle input to cache");
-        aoc_input
-    } else {
-        fs::read_to_string(input_path).expect("unable to read input from cache")
-    };
+    let cache_path = dir.join(profile.cache_file_name(year, day));
 
-    TokenTree::Literal(proc_macro::Literal::string(&aoc_input)).into()
+    let input_path =
+        aoc_client::locate_input(&project_path, &dir, &cache_path, &profile, year, day)?;
+    Ok(include_str(&input_path))
 }
 
-fn load_from_aoc(year: u32, day: u32) -> String {
+/// Looks up an item, described by `what` in errors, in the puzzle page for the given year and day,
+/// returning it with the path of the page.
+///
+/// A page saved in the project input directory is used as it is. Otherwise the page is only
+/// downloaded into the cache if it is not cached yet, or if the item is missing because part two
+/// is not shown yet and `AOC_REFRESH` is set. Downloading on every expansion would send a request
+/// for every build and every editor check until part one is solved.
+fn puzzle_item<T>(
+    year: u32,
+    day: u32,
+    what: &str,
+    item: impl Fn(&Puzzle) -> Option<T>,
+) -> Result<(PathBuf, T), String> {
     let _ = dotenv::dotenv();
 
-    let session = std::env::var("AOC_SESSION").expect("failed to load session id from AOC_SESSION");
-
-    let session = HeaderValue::from_str(&format!("session={session}"))
-        .expect("failed to construct session from AOC_SESSION");
-    let content_type = HeaderValue::from_static("text/plain");
-    let user_agent = HeaderValue::from_static("jamincan/aoc2023");
-
-    let mut headers = HeaderMap::new();
-    headers.insert(header::COOKIE, session);
-    headers.insert(header::CONTENT_TYPE, content_type);
-    headers.insert(header::USER_AGENT, user_agent);
-
-    let client = Client::builder()
-        .default_headers(headers)
-        .redirect(Policy::none())
-        .build()
-        .expect("failed to build http client");
-
-    let response = client
-        .get(&format!("https://adventofcode.com/{year}/day/{day}/input"))
-        .send()
-        .and_then(|res| res.error_for_status())
-        .unwrap_or_else(|err| panic!("failed to get input: {err}"));
-
-    response
-        .text()
-        .expect("failed to read input from adventofcode.com")
+    let profile = Profile::from_env();
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let project_path =
+        profile.project_puzzle_path(&aoc_client::inputs_dir(Path::new(&manifest_dir)), year, day);
+    if project_path.exists() {
+        let puzzle = read_puzzle(&project_path)?;
+        return match item(&puzzle) {
+            Some(item) => Ok((project_path, item)),
+            None if puzzle.has_part2() => Err(format!("{year} day {day} has no {what}")),
+            None => Err(format!(
+                "{year} day {day} has no {what} in {}, which does not show part two yet; once \
+                 part one is solved, save the page there again",
+                project_path.display()
+            )),
+        };
+    }
+
+    let dir = scratch::path("include_aoc");
+    let page_path = dir.join(profile.puzzle_file_name(year, day));
+    let client = AocClient::from_env(&profile).map_err(|err| err.to_string())?;
+    let offline = aoc_client::offline();
+
+    let load = |refresh| -> Result<(PathBuf, Puzzle), String> {
+        let path = puzzle::cached_puzzle(&dir, &page_path, year, day, &client, offline, refresh)
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| {
+                format!(
+                    "no puzzle page for {year} day {day} in {} and AOC_OFFLINE is set; unset \
+                     AOC_OFFLINE to download it",
+                    page_path.display()
+                )
+            })?;
+        let puzzle = read_puzzle(&path)?;
+        Ok((path, puzzle))
+    };
+
+    let (mut path, mut puzzle) = load(false)?;
+    if item(&puzzle).is_none() && !puzzle.has_part2() && aoc_client::refresh() && !offline {
+        (path, puzzle) = load(true)?;
+    }
+    match item(&puzzle) {
+        Some(item) => Ok((path, item)),
+        None if puzzle.has_part2() => Err(format!("{year} day {day} has no {what}")),
+        None => Err(format!(
+            "{year} day {day} has no {what} in {}, which does not show part two yet; once part \
+             one is solved, build with AOC_REFRESH set to download the page again",
+            path.display()
+        )),
+    }
+}
+
+fn read_puzzle(path: &Path) -> Result<Puzzle, String> {
+    let page = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    Ok(Puzzle::parse(&page))
+}
+
+/// Expands to `value` in a block that also includes the file at `path`, so that cargo rebuilds the
+/// calling crate whenever the file changes.
+fn tracked(path: &Path, value: Literal) -> TokenStream {
+    let mut block: TokenStream = [
+        TokenTree::Ident(Ident::new("const", Span::call_site())),
+        TokenTree::Ident(Ident::new("_", Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("str", Span::call_site())),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+    ]
+    .into_iter()
+    .collect();
+    block.extend(include_str(path));
+    block.extend([
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+        TokenTree::Literal(value),
+    ]);
+    TokenTree::Group(Group::new(Delimiter::Brace, block)).into()
+}
+
+/// Expands to `::core::include_str!("{path}")` rather than a literal so that cargo tracks the input
+/// file and rebuilds the calling crate whenever it changes. The macro is named by its full path so
+/// that an `include_str!` defined in the calling crate cannot shadow it.
+fn include_str(path: &Path) -> TokenStream {
+    let path = Literal::string(&path.to_string_lossy());
+    let mut tokens = TokenStream::new();
+    for segment in ["core", "include_str"] {
+        tokens.extend(path_separator());
+        tokens.extend([TokenTree::Ident(Ident::new(segment, Span::call_site()))]);
+    }
+    tokens.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenTree::Literal(path).into(),
+        )),
+    ]);
+    tokens
+}
+
+/// The `::` before a segment of a path.
+fn path_separator() -> [TokenTree; 2] {
+    [
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+    ]
 }
@@ -1,11 +1,77 @@
-use aoc2023::SOLUTIONS;
-use criterion::{criterion_group, criterion_main, Criterion};
+use aoc2023::{solutions, synthetic::generator, variants, SolutionSet, DEFAULT_VARIANT};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
+};
 
+/// Sizes of the synthetic inputs, in records.
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+/// Benchmarks each day in a `day{day}` group: parsing the embedded input, then every variant of
+/// each part from the input parsed up front. Parts that fail on the input, such as those not yet
+/// implemented, are skipped. Days with a generator are also run on synthetic inputs of increasing
+/// size in a `day{day}-scaling` group, which can be selected with `cargo bench -- scaling`.
+///
+/// Set `AOC_BENCH_THROUGHPUT` to also report the bytes of input processed per second.
 pub fn benchmark(c: &mut Criterion) {
-    for (day, (pt1, pt2)) in SOLUTIONS.iter().enumerate() {
-        let day = day + 1;
-        c.bench_function(&format!("d{day}p1"), |b| b.iter(|| pt1()));
-        c.bench_function(&format!("d{day}p2"), |b| b.iter(|| pt2()));
+    let throughput = std::env::var("AOC_BENCH_THROUGHPUT")
+        .is_ok_and(|val| !matches!(val.as_str(), "" | "0" | "false"));
+
+    for set in solutions() {
+        let mut group = c.benchmark_group(format!("day{}", set.day));
+        bench_input(&mut group, set, (set.input)(), None, throughput);
+        group.finish();
+
+        let Some(generator) = generator(set.year, set.day) else {
+            continue;
+        };
+        let mut group = c.benchmark_group(format!("day{}-scaling", set.day));
+        for size in SIZES {
+            let input = (generator.generate)(size);
+            bench_input(&mut group, set, &input, Some(size), throughput);
+        }
+        group.finish();
+    }
+}
+
+/// Benchmarks parsing `input` and solving each part from it, with `size` as the parameter of each
+/// benchmark for synthetic inputs.
+fn bench_input(
+    group: &mut BenchmarkGroup<WallTime>,
+    set: &SolutionSet,
+    input: &str,
+    size: Option<usize>,
+    throughput: bool,
+) {
+    let id = |name: &str| match size {
+        Some(size) => BenchmarkId::new(name, size),
+        None => BenchmarkId::from_parameter(name),
+    };
+    if throughput {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+    }
+
+    let parsed = match (set.parse)(input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("Skipping day {}: {err:#}", set.day);
+            return;
+        }
+    };
+    group.bench_function(id("parse"), |b| b.iter(|| (set.parse)(input)));
+
+    for part in [1, 2] {
+        for (variant, solution) in variants(set.year, set.day, part) {
+            let name = match variant {
+                DEFAULT_VARIANT => format!("part{part}"),
+                variant => format!("part{part}-{variant}"),
+            };
+            if let Err(err) = solution(&parsed) {
+                eprintln!("Skipping day {} {name}: {err:#}", set.day);
+                continue;
+            }
+            group.bench_function(id(&name), |b| b.iter(|| solution(&parsed)));
+        }
     }
 }
 
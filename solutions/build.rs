@@ -0,0 +1,67 @@
+//! Cargo cannot see which files and settings `include_aoc!` reads, so the solutions are rebuilt
+//! whenever the settings that decide which inputs are embedded, or the inputs directory, change.
+//!
+//! The commit the runner is built from is also captured here as `AOC_GIT_COMMIT`, so that timings
+//! are recorded against the code that produced them rather than whatever is checked out when the
+//! runner is run.
+
+use std::{path::Path, process::Command};
+
+fn main() {
+    for var in [
+        "AOC_PROFILE",
+        "AOC_INPUTS",
+        "AOC_OFFLINE",
+        "AOC_BASE_URL",
+        "AOC_REFRESH",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    // A path that does not exist would rerun this script on every build, so until the inputs
+    // directory is created, watch the directory it will be created in.
+    let inputs = std::env::var("AOC_INPUTS").unwrap_or_else(|_| "inputs".to_string());
+    let inputs = Path::new(&inputs);
+    let watched = match inputs.parent() {
+        Some(parent) if !inputs.exists() => parent,
+        _ => inputs,
+    };
+    let watched = if watched.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        watched
+    };
+    println!("cargo:rerun-if-changed={}", watched.display());
+
+    println!("cargo:rustc-env=AOC_GIT_COMMIT={}", commit());
+    // Committing moves the branch, staging changes the index and editing the sources makes the
+    // checkout dirty. Paths that do not exist would rerun this script on every build.
+    let mut sources = vec!["src".to_string()];
+    sources.extend(git(&["rev-parse", "--git-path", "HEAD"]));
+    sources.extend(git(&["rev-parse", "--git-path", "index"]));
+    if let Some(branch) = git(&["symbolic-ref", "--quiet", "HEAD"]) {
+        sources.extend(git(&["rev-parse", "--git-path", &branch]));
+    }
+    for path in sources.iter().filter(|path| Path::new(path).exists()) {
+        println!("cargo:rerun-if-changed={path}");
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The abbreviated commit, with `-dirty` appended if there are uncommitted changes.
+fn commit() -> String {
+    let Some(commit) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(changes) if changes.is_empty() => commit,
+        _ => format!("{commit}-dirty"),
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Int(i64),
+    Unsigned(u64),
+    Text(String),
+    /// Rows of characters, for answers that are read off a drawing.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Builds a grid answer from its rows.
+    pub fn grid<I>(rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Answer::Grid(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// The answer as a number, if it is one.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Int(value) => Some(*value as i128),
+            Answer::Unsigned(value) => Some(*value as i128),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+
+    /// Whether the answer matches one given as text, such as a stored or submitted answer. Numbers
+    /// are compared by value, and text ignores surrounding whitespace.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = expected.trim();
+        match self.as_i128() {
+            Some(value) => expected.parse::<i128>() == Ok(value),
+            None => self.to_string().trim() == expected,
+        }
+    }
+}
+
+/// Numbers are equal when their values are, whichever type they were returned as.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Grid(rows) => f.write_str(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($variant:ident($inner:ty): $($ty:ty),+) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::$variant(value as $inner)
+                }
+            }
+        )+
+    };
+}
+
+from_int!(Int(i64): i8, i16, i32, i64, isize);
+from_int!(Unsigned(u64): u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn compare() {
+        assert_eq!(Answer::from(46i64), Answer::from(46u32));
+        assert_ne!(Answer::from(-1i32), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(46u32), Answer::from("46"));
+        assert!(Answer::from(46u64).matches("46\n"));
+        assert!(!Answer::from(46u64).matches("460"));
+        assert!(Answer::from("ABC").matches(" ABC "));
+
+        let grid = Answer::grid(["#..", ".#."]);
+        assert_eq!(grid.to_string(), "#..\n.#.");
+        assert!(grid.matches("#..\n.#.\n"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use crate::{
+    driver::{disagreement, print_error, Sink, Time},
+    timed_out, Run, DEFAULT_VARIANT,
+};
+
+/// How long to run something before timing it, unless that takes more runs than will be timed.
+const WARM_UP: Duration = Duration::from_millis(100);
+
+/// Summary of the times taken by repeated runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The population standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarizes the times of each run, or returns `None` if there are none.
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let runs = samples.len();
+        let min = *samples.first()?;
+        let median = if runs % 2 == 1 {
+            samples[runs / 2]
+        } else {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Some(Stats {
+            runs,
+            min,
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}  stddev {:>10.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+/// Runs `f` for up to [`WARM_UP`], then times `runs` more runs of it, which must be at least one.
+/// Fails with the first error `f` returns.
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    let start = Instant::now();
+    for _ in 0..runs {
+        black_box(f()?);
+        if start.elapsed() >= WARM_UP {
+            break;
+        }
+    }
+    let samples = (0..runs)
+        .map(|_| {
+            let now = Instant::now();
+            black_box(f()?);
+            Ok(now.elapsed())
+        })
+        .collect::<Result<_>>()?;
+    Ok(Stats::new(samples).expect("at least one run"))
+}
+
+/// How a timing is labelled: `parse` for parsing the input, otherwise the part and any variant.
+pub fn label(part: Option<u8>, variant: &str) -> String {
+    match (part, variant) {
+        (None, _) => "parse".to_string(),
+        (Some(part), DEFAULT_VARIANT) => format!("part {part}"),
+        (Some(part), variant) => format!("part {part} ({variant})"),
+    }
+}
+
+/// Passes the statistics of each step of a benchmark to its function as soon as they are known,
+/// with the day, the part or `None` for parsing, and the variant, and prints errors and skipped
+/// parts to stderr.
+/// Used with [`Mode::Bench`](crate::driver::Mode::Bench).
+pub struct Bench<F>(pub F);
+
+impl<F: FnMut(u8, Option<u8>, &'static str, Stats)> Sink for Bench<F> {
+    fn no_input(&mut self, _day: u8, err: anyhow::Error) {
+        print_error(&err);
+    }
+
+    fn parsed(&mut self, day: u8, result: Result<Time>) {
+        match result {
+            Ok(time) => {
+                let stats = time.stats.expect("benchmarks collect statistics");
+                (self.0)(day, None, DEFAULT_VARIANT, stats);
+            }
+            Err(err) => print_error(&err),
+        }
+    }
+
+    fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>) {
+        let runs = match runs {
+            Ok(runs) => runs,
+            Err(err) => return print_error(&err),
+        };
+        if let Some(err) = disagreement(day, part, &runs) {
+            print_error(&err);
+        }
+        for run in runs {
+            let skipped = timed_out(&run);
+            match run.result {
+                Err(err) if skipped => eprintln!(
+                    "Skipped day {day} {}: {}",
+                    label(Some(part), run.variant),
+                    err.root_cause()
+                ),
+                Ok(_) => {
+                    let stats = run.time.stats.expect("benchmarks collect statistics");
+                    (self.0)(day, Some(part), run.variant, stats);
+                }
+                Err(err) => print_error(&err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(vec![ms(4), ms(1), ms(10), ms(3), ms(2)]).unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(4));
+        // The square root of (9 + 4 + 1 + 0 + 36) / 5 milliseconds.
+        assert_eq!(stats.stddev.as_micros(), 3162);
+
+        assert_eq!(Stats::new(vec![ms(1), ms(3)]).unwrap().median, ms(2));
+        assert!(Stats::new(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use anyhow::Result;
+use aoc_client::{answers::Answers, Profile};
+
+use crate::{
+    input::{answers_path, InputSource},
+    solution, Answer, YEAR,
+};
+
+/// The result of comparing one part's answer to the stored answer.
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Answer,
+    },
+    /// There is no stored answer to compare against.
+    Unknown(Answer),
+    Error(anyhow::Error),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Status::Unknown(actual) => write!(f, "unknown ({actual})"),
+            Status::Error(err) => write!(f, "ERROR ({err:#})"),
+        }
+    }
+}
+
+/// Runs every part of every registered day against the profile's input and compares the results
+/// to the profile's stored answers. The embedded input is used when no profile is given.
+///
+/// `report` is called with the status of each day and part as soon as it is known.
+pub fn check(profile: Option<Profile>, mut report: impl FnMut(u8, u8, Status)) -> Result<()> {
+    let source = profile
+        .clone()
+        .map_or(InputSource::Embedded, InputSource::Profile);
+    let answers = Answers::load(answers_path(&profile.unwrap_or_default()))?;
+
+    for day in crate::days(YEAR) {
+        let set = solution(YEAR, day).unwrap();
+        let parsed = source.load(day).and_then(|input| (set.parse)(&input));
+        for (part, solution) in [(1, set.part1), (2, set.part2)] {
+            let actual = match &parsed {
+                Ok(parsed) => solution(parsed),
+                Err(err) => Err(anyhow::anyhow!("{err:#}")),
+            };
+            let status = match (actual, answers.get(day as u32, part)) {
+                (Err(err), _) => Status::Error(err),
+                (Ok(actual), None) => Status::Unknown(actual),
+                (Ok(actual), Some(expected)) if actual.matches(expected) => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+            report(day, part, status);
+        }
+    }
+    Ok(())
+}
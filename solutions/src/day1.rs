@@ -3,7 +3,12 @@ use include_aoc::include_aoc;
 
 static INPUT: &str = include_aoc!(2023, 1);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 1, INPUT, parse, pt1, pt2);
+
+/// The parts read the digits on each line differently, so parsing only splits the lines.
+fn parse(input: &str) -> Result<Vec<String>> {
+    Ok(input.lines().map(|line| line.trim().to_string()).collect())
+}
 
 fn pt1_parse(value: &str) -> Result<u32> {
     let mut chars = value.chars();
@@ -14,10 +19,10 @@ fn pt1_parse(value: &str) -> Result<u32> {
     Ok(first * 10 + last)
 }
 
-fn pt1(input: &str) -> Result<u32> {
-    input
-        .lines()
-        .map(pt1_parse)
+fn pt1(lines: &[String]) -> Result<u32> {
+    lines
+        .iter()
+        .map(|line| pt1_parse(line))
         .try_fold(0, |sum, c| c.map(|c| sum + c))
 }
 
@@ -44,10 +49,10 @@ fn pt2_parse(input: &str) -> Result<u32> {
     Ok((10 * first + last) as u32)
 }
 
-fn pt2(input: &str) -> Result<u32> {
-    input
-        .lines()
-        .map(pt2_parse)
+fn pt2(lines: &[String]) -> Result<u32> {
+    lines
+        .iter()
+        .map(|line| pt2_parse(line))
         .try_fold(0, |sum, c| c.map(|c| sum + c))
 }
 
@@ -68,11 +73,13 @@ mod test {
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT1).unwrap(), 142);
+        let input = super::parse(INPUT1).unwrap();
+        assert_eq!(super::pt1(&input).unwrap(), 142);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT2).unwrap(), 281);
+        let input = super::parse(INPUT2).unwrap();
+        assert_eq!(super::pt2(&input).unwrap(), 281);
     }
 }
@@ -4,7 +4,7 @@ use itertools::Itertools;
 
 static INPUT: &str = include_aoc!(2023, 10);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 10, INPUT, parse, pt1, pt2);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Pipe {
@@ -45,6 +45,7 @@ enum RelativePosition {
     Unknown,
 }
 
+#[derive(Clone)]
 struct Map {
     pipes: Vec<(Pipe, RelativePosition)>,
     width: isize,
@@ -219,8 +220,13 @@ impl std::str::FromStr for Map {
     }
 }
 
-fn pt1(input: &str) -> Result<i32> {
-    let mut map: Map = input.parse()?;
+fn parse(input: &str) -> Result<Map> {
+    input.parse()
+}
+
+/// Both parts mark the cells of their own copy of the map as they follow the loop.
+fn pt1(map: &Map) -> Result<i32> {
+    let mut map = map.clone();
     let (start, mut current) = map.start_paths()?;
     let mut prev = start;
     let mut count = 1;
@@ -238,8 +244,8 @@ fn pt1(input: &str) -> Result<i32> {
     Ok(count / 2)
 }
 
-fn pt2(input: &str) -> Result<i32> {
-    let mut map: Map = input.parse()?;
+fn pt2(map: &Map) -> Result<i32> {
+    let mut map = map.clone();
     let (start, mut current) = map.start_paths()?;
     let mut prev = start;
     // Mark the loop
@@ -341,20 +347,16 @@ mod test {
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT1).unwrap(), 4);
-        assert_eq!(super::pt1(INPUT2).unwrap(), 8);
+        let pt1 = |input| super::pt1(&super::parse(input).unwrap()).unwrap();
+        assert_eq!(pt1(INPUT1), 4);
+        assert_eq!(pt1(INPUT2), 8);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT3).unwrap(), 4);
-        assert_eq!(super::pt2(INPUT4).unwrap(), 8);
-        assert_eq!(super::pt2(INPUT5).unwrap(), 10);
-    }
-
-    #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 7005);
-        assert_eq!(super::pt2(super::INPUT).unwrap(), 417);
+        let pt2 = |input| super::pt2(&super::parse(input).unwrap()).unwrap();
+        assert_eq!(pt2(INPUT3), 4);
+        assert_eq!(pt2(INPUT4), 8);
+        assert_eq!(pt2(INPUT5), 10);
     }
 }
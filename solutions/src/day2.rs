@@ -1,9 +1,12 @@
 use anyhow::{anyhow, Context, Result};
 use include_aoc::include_aoc;
 
+use crate::synthetic::Rng;
+
 static INPUT: &str = include_aoc!(2023, 2);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 2, INPUT, parse, pt1, pt2);
+generator!(2023, 2, synthetic);
 
 #[derive(Clone, Debug, PartialEq)]
 struct Game {
@@ -28,6 +31,10 @@ fn parse_game(input: &str) -> Result<Game> {
     Ok(Game { id, sets })
 }
 
+fn parse(input: &str) -> Result<Vec<Game>> {
+    input.lines().map(parse_game).collect()
+}
+
 fn parse_set(input: &str) -> Result<(u32, u32, u32)> {
     let (mut r, mut g, mut b) = (0, 0, 0);
     let cubes = input.split(", ");
@@ -46,13 +53,12 @@ fn parse_set(input: &str) -> Result<(u32, u32, u32)> {
     Ok((r, g, b))
 }
 
-fn pt1(input: &str) -> Result<u32> {
+fn pt1(games: &[Game]) -> Result<u32> {
     const R_MAX: u32 = 12;
     const G_MAX: u32 = 13;
     const B_MAX: u32 = 14;
-    let games: Vec<Game> = input.lines().map(parse_game).collect::<Result<_>>()?;
     Ok(games
-        .into_iter()
+        .iter()
         .filter(|game| {
             game.sets
                 .iter()
@@ -62,8 +68,7 @@ fn pt1(input: &str) -> Result<u32> {
         .sum())
 }
 
-fn pt2(input: &str) -> Result<u32> {
-    let games: Vec<Game> = input.lines().map(parse_game).collect::<Result<_>>()?;
+fn pt2(games: &[Game]) -> Result<u32> {
     games
         .iter()
         .map(|game| {
@@ -80,6 +85,22 @@ fn pt2(input: &str) -> Result<u32> {
         .try_fold(0, |sum, set| set.map(|(r, g, b)| sum + (r * g * b)))
 }
 
+/// `size` games of up to six draws, each with between 1 and 20 cubes of every colour.
+fn synthetic(size: usize) -> String {
+    let mut rng = Rng::new(2);
+    let mut input = String::new();
+    for id in 1..=size {
+        let sets: Vec<_> = (0..rng.range(1..7))
+            .map(|_| {
+                let (r, g, b) = (rng.range(1..21), rng.range(1..21), rng.range(1..21));
+                format!("{r} red, {g} green, {b} blue")
+            })
+            .collect();
+        input += &format!("Game {id}: {}\n", sets.join("; "));
+    }
+    input
+}
+
 #[cfg(test)]
 mod test {
     const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
@@ -104,13 +125,13 @@ mod test {
 
     #[test]
     fn pt1() {
-        let result = super::pt1(INPUT);
+        let result = super::pt1(&super::parse(INPUT).unwrap());
         assert_eq!(result.unwrap(), 8);
     }
 
     #[test]
     fn pt2() {
-        let result = super::pt2(INPUT);
+        let result = super::pt2(&super::parse(INPUT).unwrap());
         assert_eq!(result.unwrap(), 2286);
     }
 }
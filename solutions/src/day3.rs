@@ -4,7 +4,7 @@ use itertools::Itertools;
 
 static INPUT: &str = include_aoc!(2023, 3);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 3, INPUT, parse, pt1, pt2);
 
 fn neighbour_indices(current: usize, width: usize, num_len: usize) -> impl Iterator<Item = usize> {
     let current = current as i32;
@@ -26,21 +26,46 @@ fn neighbour_indices(current: usize, width: usize, num_len: usize) -> impl Itera
         .map(|index| index as usize)
 }
 
-fn pt1(input: &str) -> Result<u32> {
+/// The engine schematic with its line breaks normalized, so that numbers are not read across lines.
+struct Schematic {
+    chars: Vec<char>,
+    /// Length of a line, including its line break.
+    width: usize,
+    /// The index of the first digit, the length and the value of each number.
+    numbers: Vec<(usize, usize, u32)>,
+}
+
+fn parse(input: &str) -> Result<Schematic> {
     // Normalize line breaks; if we just join the lines without the break, we might end up reading numbers across lines
     let input = input.lines().join("\n");
 
     let digit = regex::Regex::new(r"\d+").unwrap();
     let width = input.lines().next().context("input is empty")?.len() + 1; // Include newline
     let chars = input.chars().collect_vec();
+    let numbers = digit
+        .find_iter(&input)
+        .map(|m| Ok((m.start(), m.len(), m.as_str().parse::<u32>()?)))
+        .collect::<Result<_>>()?;
+    Ok(Schematic {
+        chars,
+        width,
+        numbers,
+    })
+}
+
+fn pt1(schematic: &Schematic) -> Result<u32> {
+    let Schematic {
+        chars,
+        width,
+        numbers,
+    } = schematic;
     let mut valid = Vec::new();
-    'outer: for m in digit.find_iter(&input) {
-        let neighbours = neighbour_indices(m.start(), width, m.len());
+    'outer: for &(start, len, num) in numbers {
+        let neighbours = neighbour_indices(start, *width, len);
         for neighbour in neighbours {
             if let Some('.' | '0'..='9') | None = chars.get(neighbour) {
                 continue;
             } else {
-                let num = m.as_str().parse::<u32>()?;
                 valid.push(num);
                 continue 'outer;
             }
@@ -49,21 +74,19 @@ fn pt1(input: &str) -> Result<u32> {
     Ok(valid.iter().sum::<u32>())
 }
 
-fn pt2(input: &str) -> Result<u32> {
-    // Normalize line breaks
-    let input = input.lines().join("\n");
-
-    let digit = regex::Regex::new(r"\d+").unwrap();
-    let width = input.lines().next().context("input is empty")?.len() + 1; // Include newline
-    let chars = input.chars().collect_vec();
+fn pt2(schematic: &Schematic) -> Result<u32> {
+    let Schematic {
+        chars,
+        width,
+        numbers,
+    } = schematic;
 
     // map of each index associated with a * symbol and its gear count and calculated gear ratio
     let mut possible_ratios = std::collections::BTreeMap::<usize, (u32, u32)>::new();
-    for m in digit.find_iter(&input) {
-        let neighbours = neighbour_indices(m.start(), width, m.len());
+    for &(start, len, num) in numbers {
+        let neighbours = neighbour_indices(start, *width, len);
         for neighbour in neighbours {
             if let Some('*') = chars.get(neighbour) {
-                let num = m.as_str().parse::<u32>()?;
                 possible_ratios
                     .entry(neighbour)
                     .and_modify(|(count, ratio)| {
@@ -118,17 +141,13 @@ mod test {
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT).unwrap(), 4361);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt1(&input).unwrap(), 4361);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT).unwrap(), 467835);
-    }
-
-    #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 544433);
-        assert_eq!(super::pt2(super::INPUT).unwrap(), 76314915);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt2(&input).unwrap(), 467835);
     }
 }
@@ -1,11 +1,12 @@
-use std::str::Lines;
+use std::{ops::Range, str::Lines};
 
 use anyhow::{bail, Context, Result};
 use include_aoc::include_aoc;
 
 static INPUT: &str = include_aoc!(2023, 5);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 5, INPUT, parse, pt1, pt2);
+variant!(2023, 5, 2, "interval", parse, pt2_interval);
 
 #[derive(Debug)]
 struct Transformer {
@@ -14,7 +15,7 @@ struct Transformer {
 
 #[derive(Debug)]
 struct Map {
-    src_range: std::ops::Range<i64>,
+    src_range: Range<i64>,
     delta: i64,
 }
 
@@ -36,6 +37,33 @@ impl Transformer {
         }
         return transformed;
     }
+
+    /// Maps a range of values at once, splitting it wherever it crosses the edge of a map.
+    fn transform_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        let mut unmapped = vec![range];
+        let mut mapped = Vec::new();
+        for map in self.maps.iter() {
+            let mut remaining = Vec::new();
+            for range in unmapped {
+                let start = range.start.max(map.src_range.start);
+                let end = range.end.min(map.src_range.end);
+                if start >= end {
+                    remaining.push(range);
+                    continue;
+                }
+                mapped.push(start + map.delta..end + map.delta);
+                if range.start < start {
+                    remaining.push(range.start..start);
+                }
+                if end < range.end {
+                    remaining.push(end..range.end);
+                }
+            }
+            unmapped = remaining;
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
 }
 
 fn parse_transformer(input: &mut Lines) -> Result<Transformer> {
@@ -74,9 +102,15 @@ fn parse_seed_section(input: &mut Lines) -> Result<Vec<i64>> {
         .collect()
 }
 
-fn pt1(input: &str) -> Result<i64> {
+/// The seed numbers, which part 2 reads as ranges, and the maps to apply to them in order.
+struct Almanac {
+    seeds: Vec<i64>,
+    transformers: Vec<Transformer>,
+}
+
+fn parse(input: &str) -> Result<Almanac> {
     let mut lines = input.trim().lines();
-    let mut seeds = parse_seed_section(&mut lines)?;
+    let seeds = parse_seed_section(&mut lines)?;
 
     let _empty = lines
         .next()
@@ -87,7 +121,15 @@ fn pt1(input: &str) -> Result<i64> {
         transformers.push(transformer);
     }
 
-    for transformer in transformers {
+    Ok(Almanac {
+        seeds,
+        transformers,
+    })
+}
+
+fn pt1(almanac: &Almanac) -> Result<i64> {
+    let mut seeds = almanac.seeds.clone();
+    for transformer in almanac.transformers.iter() {
         for seed in seeds.iter_mut() {
             *seed = transformer.transform(*seed);
         }
@@ -96,27 +138,25 @@ fn pt1(input: &str) -> Result<i64> {
     seeds.into_iter().min().context("no minimum found")
 }
 
-fn pt2(input: &str) -> Result<i64> {
-    let mut lines = input.trim().lines();
-    let seed_nums = parse_seed_section(&mut lines)?;
-    let seed_ranges: Vec<_> = seed_nums
+/// The seed ranges of part 2, leaving out empty ones, which contain no seeds.
+fn seed_ranges(seeds: &[i64]) -> Vec<Range<i64>> {
+    seeds
         .chunks_exact(2)
         .map(|pair| pair[0]..pair[0] + pair[1])
-        .collect();
-
-    let _empty = lines
-        .next()
-        .context("empty line between seeds and maps is missing")?;
+        .filter(|range| !range.is_empty())
+        .collect()
+}
 
-    let mut transformers = Vec::new();
-    while let Ok(transformer) = parse_transformer(&mut lines) {
-        transformers.push(transformer);
-    }
+fn pt2(almanac: &Almanac) -> Result<i64> {
+    let seed_ranges = seed_ranges(&almanac.seeds);
 
     for location in 0..i32::MAX {
+        if location % (1 << 16) == 0 && crate::cancelled() {
+            bail!("cancelled after searching {location} locations");
+        }
         // Get original seed value from prospective location
         let mut transformed = location as i64;
-        for transformer in transformers.iter().rev() {
+        for transformer in almanac.transformers.iter().rev() {
             transformed = transformer.reverse(transformed);
         }
 
@@ -130,6 +170,21 @@ fn pt2(input: &str) -> Result<i64> {
     bail!("lowest value higher than {}", i32::MAX)
 }
 
+fn pt2_interval(almanac: &Almanac) -> Result<i64> {
+    let mut ranges = seed_ranges(&almanac.seeds);
+    for transformer in almanac.transformers.iter() {
+        ranges = ranges
+            .into_iter()
+            .flat_map(|range| transformer.transform_range(range))
+            .collect();
+    }
+    ranges
+        .iter()
+        .map(|range| range.start)
+        .min()
+        .context("no minimum found")
+}
+
 #[cfg(test)]
 mod test {
     const INPUT: &str = "seeds: 79 14 55 13
@@ -168,17 +223,27 @@ humidity-to-location map:
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT).unwrap(), 35);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt1(&input).unwrap(), 35);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT).unwrap(), 46);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt2(&input).unwrap(), 46);
+    }
+
+    #[test]
+    fn pt2_interval() {
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt2_interval(&input).unwrap(), 46);
     }
 
     #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 331445006);
-        assert_eq!(super::pt2(super::INPUT).unwrap(), 6472060);
+    fn empty_seed_range() {
+        let input = INPUT.replacen("seeds: 79 14 55 13", "seeds: 79 14 55 13 0 0", 1);
+        let input = super::parse(&input).unwrap();
+        assert_eq!(super::pt2(&input).unwrap(), 46);
+        assert_eq!(super::pt2_interval(&input).unwrap(), 46);
     }
 }
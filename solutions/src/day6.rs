@@ -1,23 +1,36 @@
+use std::str::FromStr;
+
 use anyhow::{Context, Result};
 use include_aoc::include_aoc;
 
 static INPUT: &str = include_aoc!(2023, 6);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 6, INPUT, parse, pt1, pt2);
+variant!(2023, 6, 1, "search", parse, pt1_search);
+variant!(2023, 6, 2, "search", parse, pt2_search);
 
-fn pt1(input: &str) -> Result<i64> {
+/// The time and record distance of each race.
+fn parse(input: &str) -> Result<Vec<(i64, i64)>> {
     let mut lines = input.trim().lines();
     let times = lines.next().context("missing first line")?;
     let times = parse_numbers_with_prefix(times, "Time:")?;
     let distances = lines.next().context("missing second line")?;
     let distances = parse_numbers_with_prefix(distances, "Distance:")?;
-    let races = times.into_iter().zip(distances.into_iter());
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+fn pt1(races: &[(i64, i64)]) -> Result<i64> {
+    let races = races.iter().map(|&(t, d)| (t as f64, d as f64));
     let intervals = races.map(winning_time_interval);
     let ways_to_win_count = intervals.map(|(t1, t2)| t2 - t1 + 1);
     Ok(ways_to_win_count.product())
 }
 
-fn parse_numbers_with_prefix(input: &str, prefix: &str) -> Result<Vec<f64>> {
+fn parse_numbers_with_prefix<T>(input: &str, prefix: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
     let input = input
         .trim()
         .strip_prefix(prefix)
@@ -56,26 +69,52 @@ fn winning_time_interval(race: (f64, f64)) -> (i64, i64) {
     (t1.floor() as i64 + 1, t2.ceil() as i64 - 1)
 }
 
-fn parse_number_with_prefix(input: &str, prefix: &str) -> Result<f64> {
-    let input = input
-        .trim()
-        .strip_prefix(prefix)
-        .with_context(|| format!("missing prefix '{prefix}'"))?;
-    let number: String = input.trim().chars().filter(char::is_ascii_digit).collect();
-    let number = number.parse()?;
-    Ok(number)
+/// The single race the sheet describes once the spaces between the numbers are ignored.
+fn kerned_race(races: &[(i64, i64)]) -> Result<(i64, i64)> {
+    let (time, distance): (String, String) = races
+        .iter()
+        .map(|(time, distance)| (time.to_string(), distance.to_string()))
+        .unzip();
+    Ok((time.parse()?, distance.parse()?))
 }
 
-fn pt2(input: &str) -> Result<i64> {
-    let mut lines = input.trim().lines();
-    let time = lines.next().context("missing first line")?;
-    let time = parse_number_with_prefix(time, "Time:")?;
-    let distance = lines.next().context("missing second line")?;
-    let distance = parse_number_with_prefix(distance, "Distance:")?;
-    let (t1, t2) = winning_time_interval((time, distance));
+fn pt2(races: &[(i64, i64)]) -> Result<i64> {
+    let (time, distance) = kerned_race(races)?;
+    let (t1, t2) = winning_time_interval((time as f64, distance as f64));
     Ok(t2 - t1 + 1)
 }
 
+/// Counts the ways to beat a race using only integers, by binary searching for the shortest winning
+/// wait. The distance travelled rises until half the race time and then falls symmetrically.
+fn ways_to_win(time: i64, record: i64) -> i64 {
+    let distance = |wait: i64| wait * (time - wait);
+    let (mut lo, mut hi) = (0, time / 2);
+    if distance(hi) <= record {
+        return 0;
+    }
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if distance(mid) > record {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    time - 2 * lo + 1
+}
+
+fn pt1_search(races: &[(i64, i64)]) -> Result<i64> {
+    Ok(races
+        .iter()
+        .map(|&(time, record)| ways_to_win(time, record))
+        .product())
+}
+
+fn pt2_search(races: &[(i64, i64)]) -> Result<i64> {
+    let (time, distance) = kerned_race(races)?;
+    Ok(ways_to_win(time, distance))
+}
+
 #[cfg(test)]
 mod test {
     const INPUT: &str = "Time:      7  15   30
@@ -92,17 +131,21 @@ Distance:  9  40  200";
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT).unwrap(), 288);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt1(&input).unwrap(), 288);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT).unwrap(), 71503);
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::kerned_race(&input).unwrap(), (71530, 940200));
+        assert_eq!(super::pt2(&input).unwrap(), 71503);
     }
 
     #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 2344708);
-        assert_eq!(super::pt2(super::INPUT).unwrap(), 30125202);
+    fn search() {
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::pt1_search(&input).unwrap(), 288);
+        assert_eq!(super::pt2_search(&input).unwrap(), 71503);
     }
 }
@@ -2,9 +2,12 @@ use anyhow::{bail, Context, Result};
 
 use include_aoc::include_aoc;
 
+use crate::synthetic::Rng;
+
 static INPUT: &str = include_aoc!(2023, 7);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 7, INPUT, parse_hands, pt1, pt2);
+generator!(2023, 7, synthetic);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
@@ -25,9 +28,8 @@ enum Card {
     Ace,
 }
 
-fn parse_card<const PART: u8>(input: char) -> Result<Card> {
-    assert!(PART >= 1 && PART <= 2);
-
+/// Parses a card as in part 1, where `J` is a jack.
+fn parse_card(input: char) -> Result<Card> {
     use Card::*;
 
     Ok(match input {
@@ -40,8 +42,7 @@ fn parse_card<const PART: u8>(input: char) -> Result<Card> {
         '8' => Eight,
         '9' => Nine,
         'T' => Ten,
-        'J' if PART == 1 => Jack,
-        'J' if PART == 2 => Joker,
+        'J' => Jack,
         'Q' => Queen,
         'K' => King,
         'A' => Ace,
@@ -56,6 +57,15 @@ struct Hand {
 }
 
 impl Hand {
+    /// The hand as read in part 2, where every jack is a joker instead.
+    fn with_jokers(&self) -> Hand {
+        let cards = self.cards.map(|card| match card {
+            Card::Jack => Card::Joker,
+            card => card,
+        });
+        Hand { cards, ..*self }
+    }
+
     pub fn rank(&self, other: &Hand) -> std::cmp::Ordering {
         use std::cmp::Ordering;
 
@@ -96,7 +106,7 @@ impl Hand {
     }
 }
 
-fn parse_hand<const PART: u8>(input: &str) -> Result<Hand> {
+fn parse_hand(input: &str) -> Result<Hand> {
     let (cards, bid) = input
         .trim()
         .split_once(char::is_whitespace)
@@ -104,7 +114,7 @@ fn parse_hand<const PART: u8>(input: &str) -> Result<Hand> {
     let cards: Vec<Card> = cards
         .trim()
         .chars()
-        .map(|ch| parse_card::<PART>(ch))
+        .map(parse_card)
         .collect::<Result<_>>()?;
     if cards.len() != 5 {
         bail!("hand only has {} cards", input.len())
@@ -115,9 +125,9 @@ fn parse_hand<const PART: u8>(input: &str) -> Result<Hand> {
     Ok(Hand { cards, bid })
 }
 
-fn parse_hands<const PART: u8>(input: &str) -> Result<Vec<Hand>> {
+fn parse_hands(input: &str) -> Result<Vec<Hand>> {
     let hands = input.trim().lines();
-    let hands = hands.map(parse_hand::<PART>).collect::<Result<_>>()?;
+    let hands = hands.map(parse_hand).collect::<Result<_>>()?;
     Ok(hands)
 }
 
@@ -157,22 +167,37 @@ impl From<&[Card]> for HandType {
     }
 }
 
-fn calculate_winnings<const PART: u8>(input: &str) -> Result<i64> {
-    let mut hands = parse_hands::<PART>(input)?;
+fn calculate_winnings(mut hands: Vec<Hand>) -> i64 {
     hands.sort_by(|a, b| a.rank(&b));
-    Ok(hands
+    hands
         .into_iter()
         .zip(1..)
         .map(|(Hand { bid, .. }, rank)| rank * bid)
-        .sum::<i64>())
+        .sum::<i64>()
 }
 
-fn pt1(input: &str) -> Result<i64> {
-    calculate_winnings::<1>(input)
+fn pt1(hands: &[Hand]) -> Result<i64> {
+    Ok(calculate_winnings(hands.to_vec()))
 }
 
-fn pt2(input: &str) -> Result<i64> {
-    calculate_winnings::<2>(input)
+fn pt2(hands: &[Hand]) -> Result<i64> {
+    Ok(calculate_winnings(
+        hands.iter().map(Hand::with_jokers).collect(),
+    ))
+}
+
+/// `size` hands of random cards with bids below 1000.
+fn synthetic(size: usize) -> String {
+    const CARDS: [char; 13] = [
+        '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+    ];
+    let mut rng = Rng::new(7);
+    let mut input = String::new();
+    for _ in 0..size {
+        let cards: String = (0..5).map(|_| *rng.choose(&CARDS)).collect();
+        input += &format!("{cards} {}\n", rng.range(1..1000));
+    }
+    input
 }
 
 #[cfg(test)]
@@ -206,7 +231,7 @@ mod test {
     #[test]
     fn handtype_pt1_ranking() {
         use super::{Card::*, Hand};
-        let mut hands = super::parse_hands::<1>(INPUT).unwrap();
+        let mut hands = super::parse_hands(INPUT).unwrap();
         hands.sort_unstable_by(|a, b| a.rank(&b));
         assert_eq!(
             hands,
@@ -238,7 +263,11 @@ mod test {
     #[test]
     fn handtype_pt2_ranking() {
         use super::{Card::*, Hand};
-        let mut hands = super::parse_hands::<2>(INPUT).unwrap();
+        let mut hands: Vec<_> = super::parse_hands(INPUT)
+            .unwrap()
+            .iter()
+            .map(Hand::with_jokers)
+            .collect();
         hands.sort_unstable_by(|a, b| a.rank(&b));
         assert_eq!(
             hands,
@@ -269,18 +298,17 @@ mod test {
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT).unwrap(), 6440);
-        assert_eq!(super::pt1(INPUT2).unwrap(), 6592);
+        let hands = super::parse_hands(INPUT).unwrap();
+        assert_eq!(super::pt1(&hands).unwrap(), 6440);
+        let hands = super::parse_hands(INPUT2).unwrap();
+        assert_eq!(super::pt1(&hands).unwrap(), 6592);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT).unwrap(), 5905);
-        assert_eq!(super::pt2(INPUT2).unwrap(), 6839);
-    }
-
-    #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 255048101);
+        let hands = super::parse_hands(INPUT).unwrap();
+        assert_eq!(super::pt2(&hands).unwrap(), 5905);
+        let hands = super::parse_hands(INPUT2).unwrap();
+        assert_eq!(super::pt2(&hands).unwrap(), 6839);
     }
 }
@@ -6,7 +6,7 @@ use include_aoc::include_aoc;
 
 static INPUT: &str = include_aoc!(2023, 8);
 
-solution!(INPUT, pt1, pt2);
+solution!(2023, 8, INPUT, parse, pt1, pt2);
 
 #[derive(Clone, Copy)]
 enum Instruction {
@@ -28,7 +28,28 @@ fn parse_instructions(input: &str) -> Result<Vec<Instruction>> {
     Ok(instructions)
 }
 
-fn parse_nodes(input: Lines) -> Result<HashMap<&str, (&str, &str)>> {
+type Nodes = HashMap<String, (String, String)>;
+
+/// The left/right instructions and the left and right neighbours of each node.
+struct Network {
+    instructions: Vec<Instruction>,
+    nodes: Nodes,
+}
+
+fn parse(input: &str) -> Result<Network> {
+    let mut lines = input.trim().lines();
+    let instructions = parse_instructions(lines.next().context("empty input")?)?;
+    let _empty_line = lines
+        .next()
+        .context("no empty line separating instructions")?;
+    let nodes = parse_nodes(lines)?;
+    Ok(Network {
+        instructions,
+        nodes,
+    })
+}
+
+fn parse_nodes(input: Lines) -> Result<Nodes> {
     let mut nodes = HashMap::new();
     for line in input {
         let (id, next_elements) = line
@@ -45,13 +66,13 @@ fn parse_nodes(input: Lines) -> Result<HashMap<&str, (&str, &str)>> {
         let (a, b) = next_elements
             .split_once(", ")
             .context("failed to split elements")?;
-        nodes.insert(id, (a, b));
+        nodes.insert(id.to_string(), (a.to_string(), b.to_string()));
     }
     Ok(nodes)
 }
 
 fn calculate_steps(
-    nodes: &HashMap<&str, (&str, &str)>,
+    nodes: &Nodes,
     mut instructions: impl Iterator<Item = Instruction>,
     start: &str,
     end_test: fn(&str) -> bool,
@@ -61,45 +82,36 @@ fn calculate_steps(
     while !end_test(current) {
         steps += 1;
         let (left, right) = nodes
-            .get(&current)
+            .get(current)
             .with_context(|| format!("node '{current}' not found"))?;
         match instructions
             .next()
             .expect("instructions should cycle forever")
         {
-            Instruction::Left => current = *left,
-            Instruction::Right => current = *right,
+            Instruction::Left => current = left,
+            Instruction::Right => current = right,
         }
     }
     Ok(steps)
 }
 
-fn pt1(input: &str) -> Result<u64> {
-    let mut lines = input.trim().lines();
-    let instructions = parse_instructions(lines.next().context("empty input")?)?;
-    let instructions = instructions.into_iter().cycle();
-    let _empty_line = lines
-        .next()
-        .context("no empty line separating instructions")?;
-    let nodes = parse_nodes(lines)?;
-
-    calculate_steps(&nodes, instructions, "AAA", |id| id == "ZZZ")
+fn pt1(network: &Network) -> Result<u64> {
+    let instructions = network.instructions.iter().copied().cycle();
+    calculate_steps(&network.nodes, instructions, "AAA", |id| id == "ZZZ")
 }
 
-fn pt2(input: &str) -> Result<u64> {
-    let mut lines = input.trim().lines();
-    let instructions = parse_instructions(lines.next().context("empty input")?)?;
-    let instructions = instructions.into_iter().cycle();
-    let _empty_line = lines
-        .next()
-        .context("no empty line separating instructions")?;
-    let nodes = parse_nodes(lines)?;
+fn pt2(network: &Network) -> Result<u64> {
+    let Network {
+        instructions,
+        nodes,
+    } = network;
+    let instructions = instructions.iter().copied().cycle();
 
     let starting_node_steps: Vec<_> = nodes
         .keys()
         .filter(|id| id.ends_with('A'))
         .map(|node| {
-            calculate_steps(&nodes, instructions.clone(), *node, |node| {
+            calculate_steps(nodes, instructions.clone(), node, |node| {
                 node.ends_with('Z')
             })
         })
@@ -143,18 +155,15 @@ mod test {
 
     #[test]
     fn pt1() {
-        assert_eq!(super::pt1(INPUT1).unwrap(), 2);
-        assert_eq!(super::pt1(INPUT2).unwrap(), 6);
+        let network = super::parse(INPUT1).unwrap();
+        assert_eq!(super::pt1(&network).unwrap(), 2);
+        let network = super::parse(INPUT2).unwrap();
+        assert_eq!(super::pt1(&network).unwrap(), 6);
     }
 
     #[test]
     fn pt2() {
-        assert_eq!(super::pt2(INPUT3).unwrap(), 6);
-    }
-
-    #[test]
-    fn real_input() {
-        assert_eq!(super::pt1(super::INPUT).unwrap(), 19631);
-        assert_eq!(super::pt2(super::INPUT).unwrap(), 21003205388413);
+        let network = super::parse(INPUT3).unwrap();
+        assert_eq!(super::pt2(&network).unwrap(), 6);
     }
 }
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    agree,
+    bench::{label, Stats},
+    input::InputSource,
+    parse_input, run_part, timed_out, Run, Variants,
+};
+
+/// How [`drive`] runs each step.
+#[derive(Clone, Copy, Debug)]
+pub enum Mode {
+    /// Once, failing parts that take longer than `timeout`.
+    Once { timeout: Option<Duration> },
+    /// Once, then `runs` more times after a warm-up to collect statistics. Parts whose first run
+    /// takes longer than `timeout` are skipped rather than measured.
+    Bench {
+        runs: usize,
+        timeout: Option<Duration>,
+    },
+}
+
+/// How long a step took.
+#[derive(Clone, Copy, Debug)]
+pub struct Time {
+    /// The first run, or the time limit of a run that timed out.
+    pub elapsed: Duration,
+    /// Statistics of the repeated runs, when benchmarking.
+    pub stats: Option<Stats>,
+}
+
+/// Receives the outcome of each step of [`drive`] as soon as it is known. Errors already say which
+/// day and part they are about.
+pub trait Sink {
+    /// Called before the input for a day is loaded.
+    fn start_day(&mut self, _day: u8) {}
+
+    /// The input for a day could not be loaded, so none of its parts were run.
+    fn no_input(&mut self, day: u8, err: anyhow::Error);
+
+    /// The input for a day was parsed, or failed to parse, in which case none of its parts were run.
+    fn parsed(&mut self, day: u8, result: Result<Time>);
+
+    /// The selected implementations of a part ran, or none could be selected.
+    fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>);
+}
+
+/// Loads and parses the input from `source` for each day, then runs each selected part, reporting
+/// every step to `sink`. Returns whether every input loaded and parsed, every part succeeded and,
+/// where several variants were run, whether they agreed. Parts skipped by a benchmark for taking
+/// too long do not count as failures.
+pub fn drive(
+    days: &[u8],
+    parts: &[u8],
+    variants: &Variants,
+    source: &InputSource,
+    mode: Mode,
+    sink: &mut impl Sink,
+) -> bool {
+    let mut ok = true;
+    for day in days.iter().copied() {
+        sink.start_day(day);
+        let input = match source.load(day) {
+            Ok(input) => input,
+            Err(err) => {
+                sink.no_input(
+                    day,
+                    err.context(format!("failed to load input for day {day}")),
+                );
+                ok = false;
+                continue;
+            }
+        };
+        let parsed = match parse_input(day, &input, mode) {
+            Ok((parsed, time)) => {
+                sink.parsed(day, Ok(time));
+                parsed
+            }
+            Err(err) => {
+                sink.parsed(day, Err(err));
+                ok = false;
+                continue;
+            }
+        };
+        for part in parts.iter().copied() {
+            let runs = run_part(day, part, variants, &parsed, mode);
+            ok &= runs.as_ref().is_ok_and(|runs| {
+                let skipped = |run: &&Run| matches!(mode, Mode::Bench { .. }) && timed_out(run);
+                let measured: Vec<_> = runs.iter().filter(|run| !skipped(run)).collect();
+                measured.is_empty() || agree(measured)
+            });
+            sink.part(day, part, runs);
+        }
+    }
+    ok
+}
+
+/// Prints an error and its causes to stderr, with a backtrace if `RUST_BACKTRACE` is set.
+pub fn print_error(err: &anyhow::Error) {
+    eprintln!("{err:?}");
+}
+
+/// An error if every run of a part succeeded but they did not all give the same answer.
+pub fn disagreement(day: u8, part: u8, runs: &[Run]) -> Option<anyhow::Error> {
+    let succeeded = runs.iter().all(|run| run.result.is_ok());
+    (succeeded && !agree(runs)).then(|| anyhow!("variants of day {day} part {part} disagree"))
+}
+
+/// Prints how long parsing took and each answer with how long it took, for running a single day.
+pub struct Text;
+
+impl Sink for Text {
+    fn no_input(&mut self, _day: u8, err: anyhow::Error) {
+        print_error(&err);
+    }
+
+    fn parsed(&mut self, _day: u8, result: Result<Time>) {
+        match result {
+            Ok(time) => println!("Parsed input in {:.2?}", time.elapsed),
+            Err(err) => print_error(&err),
+        }
+    }
+
+    fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>) {
+        let runs = match runs {
+            Ok(runs) => runs,
+            Err(err) => return print_error(&err),
+        };
+        for run in &runs {
+            let label = label(Some(part), run.variant);
+            match &run.result {
+                Ok(answer) => println!(
+                    "Solution for {label} completed in {:.2?}:\n{answer}",
+                    run.time.elapsed
+                ),
+                Err(err) => print_error(err),
+            }
+        }
+        if let Some(err) = disagreement(day, part, &runs) {
+            print_error(&err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::{drive, Mode, Sink, Time};
+    use crate::{input::InputSource, Run, Variants};
+
+    /// Records each step as a line.
+    #[derive(Default)]
+    struct Steps(Vec<String>);
+
+    impl Sink for Steps {
+        fn no_input(&mut self, day: u8, err: anyhow::Error) {
+            self.0.push(format!("{day} no input: {err}"));
+        }
+
+        fn parsed(&mut self, day: u8, result: Result<Time>) {
+            match result {
+                Ok(_) => self.0.push(format!("{day} parsed")),
+                Err(err) => self.0.push(format!("{day} parse: {err}")),
+            }
+        }
+
+        fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>) {
+            match runs {
+                Ok(runs) => {
+                    for run in runs {
+                        match run.result {
+                            Ok(answer) => self.0.push(format!("{day}.{part} {answer}")),
+                            Err(err) => self.0.push(format!("{day}.{part}: {err}")),
+                        }
+                    }
+                }
+                Err(err) => self.0.push(format!("{day}.{part}: {err}")),
+            }
+        }
+    }
+
+    #[test]
+    fn steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        std::fs::write(&path, "1abc2\n").unwrap();
+        let source = InputSource::File(path.clone());
+        let once = Mode::Once { timeout: None };
+
+        let mut steps = Steps::default();
+        let ok = drive(
+            &[1, 9],
+            &[1, 3],
+            &Variants::Default,
+            &source,
+            once,
+            &mut steps,
+        );
+        assert!(!ok);
+        assert_eq!(
+            steps.0,
+            [
+                "1 parsed",
+                "1.1 12",
+                "1.3: failed to solve day 1 part 3",
+                "9 parse: failed to parse input for day 9",
+            ]
+        );
+
+        let mut steps = Steps::default();
+        let missing = InputSource::File(path.with_extension("missing"));
+        assert!(!drive(
+            &[1],
+            &[1],
+            &Variants::Default,
+            &missing,
+            once,
+            &mut steps
+        ));
+        assert_eq!(steps.0, ["1 no input: failed to load input for day 1"]);
+
+        let mut steps = Steps::default();
+        assert!(drive(
+            &[1],
+            &[1],
+            &Variants::Default,
+            &source,
+            once,
+            &mut steps
+        ));
+    }
+}
@@ -0,0 +1,410 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use aoc_client::lines;
+
+use crate::{
+    bench::{label, Bench, Stats},
+    driver::{drive, Mode},
+    input::{timings_path, InputSource},
+    Variants,
+};
+
+/// Where timings are measured: the code, the machine and how the runner was built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// The abbreviated commit, with `-dirty` appended if there were uncommitted changes, that the
+    /// runner was built from.
+    pub commit: String,
+    pub machine: String,
+    /// `debug` or `release`.
+    pub build: String,
+}
+
+impl Environment {
+    /// Describes the build of the runner and the machine it runs on. The machine is named by
+    /// `AOC_MACHINE` if it is set, otherwise by its host name.
+    pub fn detect() -> Self {
+        let build = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        Environment {
+            commit: env!("AOC_GIT_COMMIT").to_string(),
+            machine: machine(),
+            build: build.to_string(),
+        }
+    }
+}
+
+fn machine() -> String {
+    ["AOC_MACHINE", "HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The benchmarked time of parsing a day's input or of one implementation of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub env: Environment,
+    /// When the timing was recorded, in seconds since the Unix epoch.
+    pub recorded: u64,
+    /// Identifies the input that was used, such as `embedded` or `profile:alice`.
+    pub input: String,
+    pub day: u8,
+    /// The part, or `None` for parsing the input.
+    pub part: Option<u8>,
+    pub variant: String,
+    pub stats: Stats,
+}
+
+const HEADER: &str = "# commit\tmachine\tbuild\trecorded\tinput\tday\tpart\tvariant\truns\t\
+                      min_ns\tmedian_ns\tmean_ns\tstddev_ns\n";
+
+impl Timing {
+    /// A timing measured now.
+    pub fn new(
+        env: &Environment,
+        input: &InputSource,
+        day: u8,
+        part: Option<u8>,
+        variant: &str,
+        stats: Stats,
+    ) -> Self {
+        let recorded = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Timing {
+            env: env.clone(),
+            recorded,
+            input: input.to_string(),
+            day,
+            part,
+            variant: variant.to_string(),
+            stats,
+        }
+    }
+
+    /// Whether both timings measure the same thing under the same conditions, whatever the code.
+    fn same_benchmark(&self, other: &Timing) -> bool {
+        self.env.machine == other.env.machine
+            && self.env.build == other.env.build
+            && self.input == other.input
+            && self.day == other.day
+            && self.part == other.part
+            && self.variant == other.variant
+    }
+
+    fn line(&self) -> String {
+        let Stats {
+            runs,
+            min,
+            median,
+            mean,
+            stddev,
+        } = self.stats;
+        let part = self
+            .part
+            .map_or("parse".to_string(), |part| part.to_string());
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{part}\t{}\t{runs}\t{}\t{}\t{}\t{}\n",
+            self.env.commit,
+            self.env.machine,
+            self.env.build,
+            self.recorded,
+            self.input,
+            self.day,
+            self.variant,
+            min.as_nanos(),
+            median.as_nanos(),
+            mean.as_nanos(),
+            stddev.as_nanos(),
+        )
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t').map(str::trim);
+        let mut field = || fields.next();
+        let env = Environment {
+            commit: field()?.to_string(),
+            machine: field()?.to_string(),
+            build: field()?.to_string(),
+        };
+        let recorded = field()?.parse().ok()?;
+        let input = field()?.to_string();
+        let day = field()?.parse().ok()?;
+        let part = match field()? {
+            "parse" => None,
+            part => Some(part.parse().ok()?),
+        };
+        let variant = field()?.to_string();
+        let runs = field()?.parse().ok()?;
+        let mut nanos = || field()?.parse().ok().map(Duration::from_nanos);
+        let stats = Stats {
+            runs,
+            min: nanos()?,
+            median: nanos()?,
+            mean: nanos()?,
+            stddev: nanos()?,
+        };
+        Some(Timing {
+            env,
+            recorded,
+            input,
+            day,
+            part,
+            variant,
+            stats,
+        })
+    }
+}
+
+/// Timings recorded by earlier benchmark runs, stored oldest first as [`lines`] of tab-separated
+/// values.
+pub struct History {
+    path: PathBuf,
+    timings: Vec<Timing>,
+}
+
+impl History {
+    /// Loads the history stored at `path`, which need not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let timings = lines::load(&path, Timing::parse)?;
+        Ok(History { path, timings })
+    }
+
+    /// Appends timings to the history and its file.
+    pub fn record(&mut self, timings: Vec<Timing>) -> Result<()> {
+        let contents: String = timings.iter().map(Timing::line).collect();
+        lines::append(&self.path, HEADER, &contents)?;
+        self.timings.extend(timings);
+        Ok(())
+    }
+
+    /// The latest timing of the same benchmark from another commit, or from `commit` if it is
+    /// given. An exact match is preferred; otherwise `commit` may be abbreviated, but then only
+    /// matches timings of clean checkouts, not those marked `-dirty`.
+    pub fn baseline(&self, timing: &Timing, commit: Option<&str>) -> Option<&Timing> {
+        let mut earlier = self
+            .timings
+            .iter()
+            .rev()
+            .filter(|earlier| earlier.same_benchmark(timing));
+        match commit {
+            Some(commit) => earlier
+                .clone()
+                .find(|earlier| earlier.env.commit == commit)
+                .or_else(|| {
+                    earlier.find(|earlier| {
+                        earlier.env.commit.starts_with(commit)
+                            && !earlier.env.commit.ends_with("-dirty")
+                    })
+                }),
+            None => earlier.find(|earlier| earlier.env.commit != timing.env.commit),
+        }
+    }
+}
+
+/// How many percent slower `current` is than `baseline`, or `None` if the baseline took no
+/// measurable time.
+fn change(baseline: Duration, current: Duration) -> Option<f64> {
+    (!baseline.is_zero()).then(|| (current.as_secs_f64() / baseline.as_secs_f64() - 1.) * 100.)
+}
+
+/// Whether `current` is more than `threshold` percent slower than `baseline`.
+fn slower(baseline: Duration, current: Duration, threshold: f64) -> bool {
+    change(baseline, current).is_some_and(|change| change > threshold)
+}
+
+/// Benchmarks the selected days on their embedded input with the default implementation of both
+/// parts, prints how the median time of each compares with its baseline from the history and
+/// records the new timings. Parts whose first run takes longer than `timeout` are skipped. Returns
+/// whether everything else ran and nothing got more than `threshold` percent slower.
+///
+/// The baseline is the latest timing from `baseline` if it is given, otherwise from any other
+/// commit, on the same machine and build.
+pub fn compare(
+    days: &[u8],
+    runs: usize,
+    timeout: Duration,
+    threshold: f64,
+    baseline: Option<&str>,
+) -> Result<bool> {
+    let env = Environment::detect();
+    let mut history = History::load(timings_path())?;
+    let source = InputSource::Embedded;
+
+    let mut timings = Vec::new();
+    let mut ok = drive(
+        days,
+        &[1, 2],
+        &Variants::Default,
+        &source,
+        Mode::Bench {
+            runs,
+            timeout: Some(timeout),
+        },
+        &mut Bench(|day, part, variant, stats| {
+            timings.push(Timing::new(&env, &source, day, part, variant, stats));
+        }),
+    );
+
+    println!(
+        "Commit {} on {} ({} build), {runs} runs each:",
+        env.commit, env.machine, env.build
+    );
+    let mut rows = vec![[
+        "Day", "Part", "Baseline", "Before", "After", "Change", "Status",
+    ]
+    .map(String::from)];
+    for timing in &timings {
+        let after = timing.stats.median;
+        let (commit, before, change, status) = match history.baseline(timing, baseline) {
+            None => (String::new(), String::new(), String::new(), "new"),
+            Some(base) => {
+                let before = base.stats.median;
+                let status = if slower(before, after, threshold) {
+                    ok = false;
+                    "SLOWER"
+                } else {
+                    "ok"
+                };
+                (
+                    base.env.commit.clone(),
+                    format!("{before:.2?}"),
+                    change(before, after)
+                        .map_or_else(|| "n/a".to_string(), |change| format!("{change:+.1}%")),
+                    status,
+                )
+            }
+        };
+        rows.push([
+            timing.day.to_string(),
+            label(timing.part, &timing.variant),
+            commit,
+            before,
+            format!("{after:.2?}"),
+            change,
+            status.to_string(),
+        ]);
+    }
+    print_table(&rows);
+
+    history.record(timings)?;
+    Ok(ok)
+}
+
+/// Prints rows with their columns aligned, with the times and changes aligned to the right.
+fn print_table(rows: &[[String; 7]]) {
+    let mut widths = [0; 7];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = cell.chars().count().max(*width);
+        }
+    }
+    for row in rows {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 | 3..=5 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{change, slower, Environment, History, Timing};
+    use crate::bench::Stats;
+
+    fn timing(commit: &str, median_us: u64) -> Timing {
+        let us = Duration::from_micros;
+        Timing {
+            env: Environment {
+                commit: commit.to_string(),
+                machine: "box".to_string(),
+                build: "release".to_string(),
+            },
+            recorded: 1_700_000_000,
+            input: "embedded".to_string(),
+            day: 5,
+            part: Some(2),
+            variant: "interval".to_string(),
+            stats: Stats {
+                runs: 10,
+                min: us(median_us - 1),
+                median: us(median_us),
+                mean: us(median_us),
+                stddev: us(1),
+            },
+        }
+    }
+
+    #[test]
+    fn history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timings.tsv");
+
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(vec![timing("aaaaaaa", 100), timing("bbbbbbb", 120)])
+            .unwrap();
+        history.record(vec![timing("ccccccc", 90)]).unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.timings.len(), 3);
+        assert_eq!(history.timings[0], timing("aaaaaaa", 100));
+
+        let current = timing("ccccccc", 150);
+        let latest = history.baseline(&current, None).unwrap();
+        assert_eq!(latest.env.commit, "bbbbbbb");
+        let chosen = history.baseline(&current, Some("aaa")).unwrap();
+        assert_eq!(chosen.stats.median, Duration::from_micros(100));
+        History::load(&path)
+            .unwrap()
+            .record(vec![timing("aaaaaaa-dirty", 80), timing("ddddddd", 70)])
+            .unwrap();
+        let history = History::load(&path).unwrap();
+        let clean = history.baseline(&current, Some("aaa")).unwrap();
+        assert_eq!(clean.env.commit, "aaaaaaa");
+        let exact = history.baseline(&current, Some("aaaaaaa")).unwrap();
+        assert_eq!(exact.env.commit, "aaaaaaa");
+        let dirty = history.baseline(&current, Some("aaaaaaa-dirty")).unwrap();
+        assert_eq!(dirty.stats.median, Duration::from_micros(80));
+        let other_machine = Timing {
+            env: Environment {
+                machine: "laptop".to_string(),
+                ..current.env.clone()
+            },
+            ..current
+        };
+        assert!(history.baseline(&other_machine, None).is_none());
+    }
+
+    #[test]
+    fn threshold() {
+        let ms = Duration::from_millis;
+        assert!(!slower(ms(100), ms(109), 10.));
+        assert!(slower(ms(100), ms(111), 10.));
+        assert!(!slower(ms(100), ms(50), 0.));
+        assert!(!slower(Duration::ZERO, ms(1), 10.));
+        assert_eq!(change(Duration::ZERO, ms(1)), None);
+        assert_eq!(change(ms(100), ms(200)), Some(100.));
+    }
+}
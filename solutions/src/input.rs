@@ -0,0 +1,147 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use anyhow::{Context, Result};
+use aoc_client::Profile;
+
+use crate::{solution, YEAR};
+
+/// Where the runner reads puzzle input from.
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    /// The input embedded at compile time by `include_aoc!`.
+    Embedded,
+    /// A single file, used for every day that is run.
+    File(PathBuf),
+    /// A directory laid out like the project inputs directory, containing `day05.txt` or
+    /// `2023/day05.txt`.
+    Dir(PathBuf),
+    /// Standard input, read once and used for every day that is run.
+    Stdin,
+    /// A profile's input from the inputs directory, or from the build cache of `include_aoc!`,
+    /// where it is downloaded with the profile's session if neither has it yet.
+    Profile(Profile),
+}
+
+impl InputSource {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &Path) -> Self {
+        if arg == Path::new("-") {
+            InputSource::Stdin
+        } else if arg.is_dir() {
+            InputSource::Dir(arg.to_path_buf())
+        } else {
+            InputSource::File(arg.to_path_buf())
+        }
+    }
+
+    /// Loads the input for `day`.
+    pub fn load(&self, day: u8) -> Result<Cow<'static, str>> {
+        match self {
+            InputSource::Embedded => {
+                let set =
+                    solution(YEAR, day).with_context(|| format!("no solution for day {day}"))?;
+                Ok(Cow::Borrowed((set.input)()))
+            }
+            InputSource::File(path) => read(path),
+            InputSource::Dir(dir) => {
+                let file_name = format!("day{day:02}.txt");
+                let path = dir.join(&file_name);
+                if path.exists() {
+                    read(&path)
+                } else {
+                    read(&dir.join(YEAR.to_string()).join(file_name))
+                }
+            }
+            InputSource::Stdin => {
+                static STDIN: OnceLock<String> = OnceLock::new();
+                if let Some(input) = STDIN.get() {
+                    return Ok(Cow::Borrowed(input));
+                }
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                Ok(Cow::Borrowed(STDIN.get_or_init(|| input)))
+            }
+            InputSource::Profile(profile) => {
+                let day = day as u32;
+                let project_path = profile.project_input_path(&inputs_dir(), YEAR, day);
+                let cache_dir = cache_dir();
+                let cache_path = cache_dir.join(profile.cache_file_name(YEAR, day));
+                let path = aoc_client::locate_input(
+                    &project_path,
+                    &cache_dir,
+                    &cache_path,
+                    profile,
+                    YEAR,
+                    day,
+                )
+                .with_context(|| format!("failed to get input for profile {profile}"))?;
+                read(&path)
+            }
+        }
+    }
+}
+
+/// Identifies the source in machine-readable output.
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Embedded => f.write_str("embedded"),
+            InputSource::File(path) | InputSource::Dir(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => f.write_str("stdin"),
+            InputSource::Profile(profile) => write!(f, "profile:{profile}"),
+        }
+    }
+}
+
+/// The project inputs directory that `include_aoc!` reads, which also holds each profile's
+/// submissions and answers and the timing history. It is found from where the crate was built
+/// rather than the current directory, so that the runner keeps this bookkeeping in one place
+/// wherever it is run from.
+pub fn inputs_dir() -> PathBuf {
+    aoc_client::inputs_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+}
+
+/// The build cache of `include_aoc!`, which holds the lock, throttle and request log shared by
+/// everything that downloads from the site.
+pub fn cache_dir() -> PathBuf {
+    PathBuf::from(include_aoc::cache_dir!())
+}
+
+/// The saved inputs in [`cache_dir`] and [`inputs_dir`] that look like error pages rather than
+/// puzzle inputs, with the reason each was rejected.
+pub fn invalid_inputs() -> Result<Vec<(PathBuf, &'static str)>> {
+    let mut invalid = aoc_client::validate::invalid_inputs(&cache_dir())?;
+    invalid.extend(aoc_client::validate::invalid_inputs(&inputs_dir())?);
+    Ok(invalid)
+}
+
+/// Where a profile's known correct answers are stored, next to its inputs in [`inputs_dir`].
+pub fn answers_path(profile: &Profile) -> PathBuf {
+    profile.project_dir(&inputs_dir(), YEAR).join("answers.tsv")
+}
+
+/// Where every answer a profile has submitted is recorded, next to its inputs in [`inputs_dir`].
+pub fn submissions_path(profile: &Profile) -> PathBuf {
+    profile
+        .project_dir(&inputs_dir(), YEAR)
+        .join("submissions.tsv")
+}
+
+/// Where benchmarked timings are recorded, for every profile and input.
+pub fn timings_path() -> PathBuf {
+    inputs_dir().join("timings.tsv")
+}
+
+fn read(path: &Path) -> Result<Cow<'static, str>> {
+    std::fs::read_to_string(path)
+        .map(Cow::Owned)
+        .with_context(|| format!("failed to read input from {}", path.display()))
+}
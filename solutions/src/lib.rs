@@ -1,54 +1,428 @@
 #![feature(let_chains)]
 
-pub type Solution = fn() -> anyhow::Result<String>;
-pub type SolutionSet = (Solution, Solution);
+use std::{
+    any::Any,
+    cell::OnceCell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
+use anyhow::{anyhow, bail, ensure, Context, Result};
+
+pub use answer::Answer;
+use bench::measure;
+use driver::{Mode, Time};
+
+mod answer;
+pub mod bench;
+pub mod check;
+pub mod driver;
+pub mod history;
+pub mod input;
+pub mod output;
+pub mod scaffold;
+pub mod submit;
+pub mod summary;
+pub mod synthetic;
+
+/// The event year these solutions are for.
+pub const YEAR: u32 = 2023;
+
+/// A day's input after parsing, shared by the solutions for both of its parts and with the worker
+/// threads of runs that have a time limit.
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+pub type Parser = fn(&str) -> anyhow::Result<Parsed>;
+
+pub type Solution = fn(&Parsed) -> anyhow::Result<Answer>;
+
+/// Name of the main solution for a part, as opposed to alternatives registered with `variant!`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// The main solution for a day, registered with `solution!`.
+pub struct SolutionSet {
+    pub year: u32,
+    pub day: u8,
+    /// The input embedded at compile time.
+    pub input: fn() -> &'static str,
+    pub parse: Parser,
+    pub part1: Solution,
+    pub part2: Solution,
+}
+
+inventory::collect!(SolutionSet);
+
+/// An alternative solution for one part of a day, registered with `variant!`.
+pub struct Variant {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: Solution,
+}
+
+inventory::collect!(Variant);
+
+/// Registers the solution for a year and day, with the embedded input, the function parsing it and
+/// the functions solving each part from the parsed input. Part 2 may be left out until it is
+/// solved.
 macro_rules! solution {
-    ($input:ident, $pt1:ident, $pt2:ident) => {
-        pub const SOLUTION: crate::SolutionSet = (solution::part1, solution::part2);
-        mod solution {
-            pub fn part1() -> anyhow::Result<String> {
-                super::$pt1(super::$input).map(|res| res.to_string())
+    ($year:literal, $day:literal, $input:ident, $parse:ident, $pt1:ident, $pt2:ident) => {
+        inventory::submit! {
+            crate::SolutionSet {
+                year: $year,
+                day: $day,
+                input: || $input,
+                parse: |input| $parse(input).map(|parsed| std::sync::Arc::new(parsed) as crate::Parsed),
+                part1: |parsed| $pt1(crate::downcast(parsed, $parse)?).map(crate::Answer::from),
+                part2: |parsed| $pt2(crate::downcast(parsed, $parse)?).map(crate::Answer::from),
             }
-
-            pub fn part2() -> anyhow::Result<String> {
-                super::$pt2(super::$input).map(|res| res.to_string())
+        }
+    };
+    ($year:literal, $day:literal, $input:ident, $parse:ident, $pt1:ident) => {
+        inventory::submit! {
+            crate::SolutionSet {
+                year: $year,
+                day: $day,
+                input: || $input,
+                parse: |input| $parse(input).map(|parsed| std::sync::Arc::new(parsed) as crate::Parsed),
+                part1: |parsed| $pt1(crate::downcast(parsed, $parse)?).map(crate::Answer::from),
+                part2: |_| anyhow::bail!("Solution for part 2 not yet implemented"),
             }
         }
     };
-    ($input:ident, $pt1:ident) => {
-        pub const SOLUTION: crate::SolutionSet = (solution::part1, solution::part2);
-        mod solution {
-            pub fn part1() -> anyhow::Result<String> {
-                super::$pt1(super::$input).map(|res| res.to_string())
+}
+
+/// Registers a named alternative to the solution for one part of a year and day. The parser must
+/// be the one the day's solution was registered with.
+macro_rules! variant {
+    ($year:literal, $day:literal, $part:literal, $name:literal, $parse:ident, $solve:ident) => {
+        inventory::submit! {
+            crate::Variant {
+                year: $year,
+                day: $day,
+                part: $part,
+                name: $name,
+                solve: |parsed| $solve(crate::downcast(parsed, $parse)?).map(crate::Answer::from),
             }
+        }
+    };
+}
 
-            pub fn part2() -> anyhow::Result<String> {
-                anyhow::bail!("Solution for part 2 not yet implemented")
+/// Registers a function generating inputs for a year and day with a given number of records, for
+/// benchmarking how its solutions scale.
+macro_rules! generator {
+    ($year:literal, $day:literal, $generate:ident) => {
+        inventory::submit! {
+            crate::synthetic::Generator {
+                year: $year,
+                day: $day,
+                generate: $generate,
             }
         }
     };
 }
 
-pub fn run_solution(day: u8, part: u8) {
-    use std::time::Instant;
-    let (pt1, pt2) = SOLUTIONS[day as usize - 1];
+/// Recovers the input parsed by `parse`, which only serves to name its type.
+fn downcast<T: 'static>(parsed: &Parsed, _parse: fn(&str) -> Result<T>) -> Result<&T> {
+    (**parsed)
+        .downcast_ref()
+        .context("parsed input is not of the type the solution expects")
+}
+
+/// Every registered solution, ordered by year and day.
+pub fn solutions() -> Vec<&'static SolutionSet> {
+    let mut solutions: Vec<_> = inventory::iter::<SolutionSet>.into_iter().collect();
+    solutions.sort_by_key(|set| (set.year, set.day));
+    solutions
+}
+
+/// The solution for a day.
+pub fn solution(year: u32, day: u8) -> Option<&'static SolutionSet> {
+    inventory::iter::<SolutionSet>
+        .into_iter()
+        .find(|set| set.year == year && set.day == day)
+}
+
+/// Every implementation of a part of a day with its name, starting with [`DEFAULT_VARIANT`] and
+/// followed by the registered variants in alphabetical order. Empty if the day has no solution or
+/// the part is not 1 or 2.
+pub fn variants(year: u32, day: u8, part: u8) -> Vec<(&'static str, Solution)> {
+    let Some(set) = solution(year, day) else {
+        return Vec::new();
+    };
+    let default = match part {
+        1 => set.part1,
+        2 => set.part2,
+        _ => return Vec::new(),
+    };
+    let mut variants: Vec<_> = inventory::iter::<Variant>
+        .into_iter()
+        .filter(|variant| variant.year == year && variant.day == day && variant.part == part)
+        .map(|variant| (variant.name, variant.solve))
+        .collect();
+    variants.sort_by_key(|(name, _)| *name);
+    variants.insert(0, (DEFAULT_VARIANT, default));
+    variants
+}
+
+/// The days of a year that have a solution.
+pub fn days(year: u32) -> Vec<u8> {
+    solutions()
+        .into_iter()
+        .filter(|set| set.year == year)
+        .map(|set| set.day)
+        .collect()
+}
+
+/// Which implementations of a part the runner uses.
+#[derive(Clone, Debug, Default)]
+pub enum Variants {
+    /// Only the default solution.
+    #[default]
+    Default,
+    All,
+    Named(Vec<String>),
+}
+
+impl Variants {
+    /// The selected implementations of a part, in the order of [`variants`].
+    pub fn select(&self, year: u32, day: u8, part: u8) -> Vec<(&'static str, Solution)> {
+        let variants = variants(year, day, part).into_iter();
+        match self {
+            Variants::Default => variants.take(1).collect(),
+            Variants::All => variants.collect(),
+            Variants::Named(names) => variants
+                .filter(|(name, _)| names.iter().any(|selected| selected == name))
+                .collect(),
+        }
+    }
+}
+
+/// The outcome of running one implementation of a part.
+#[derive(Debug)]
+pub struct Run {
+    pub variant: &'static str,
+    pub result: anyhow::Result<Answer>,
+    pub time: Time,
+}
+
+/// The error of a run that did not finish within its time limit.
+#[derive(Debug)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Whether a run failed by timing out.
+pub fn timed_out(run: &Run) -> bool {
+    run.result.as_ref().is_err_and(|err| err.is::<TimedOut>())
+}
+
+/// Parses a duration such as `5s`, `500ms`, `2m` or `1h`, or a plain number of seconds. Zero is
+/// rejected, since nothing could finish within it.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(end);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("'{s}' is not a duration such as 5s or 500ms"))?;
+    let secs = match unit {
+        "" | "s" => value,
+        "ms" => value / 1000.0,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(format!("unknown unit '{unit}'; use ms, s, m or h")),
+    };
+    let duration =
+        Duration::try_from_secs_f64(secs).map_err(|err| format!("'{s}' is out of range: {err}"))?;
+    if duration.is_zero() {
+        return Err(format!(
+            "'{s}' is zero; the timeout must be longer than that"
+        ));
+    }
+    Ok(duration)
+}
+
+thread_local! {
+    /// Raised by the runner when the run on this worker thread has timed out.
+    static CANCEL: OnceCell<Arc<AtomicBool>> = const { OnceCell::new() };
+}
+
+/// Whether the run on this thread has timed out and its result will be ignored. Threads cannot be
+/// stopped from outside, so solutions that can run for a long time should check this now and then
+/// and give up, rather than keep a core busy while the parts after them are timed.
+pub fn cancelled() -> bool {
+    CANCEL.with(|cancel| {
+        cancel
+            .get()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    })
+}
 
-    let solution = match part {
-        1 => pt1,
-        2 => pt2,
-        _ => panic!("part must be 1 or 2"),
+/// Runs and times a solution, on a worker thread if it has a time limit. A run that takes longer
+/// fails with [`TimedOut`], and one that panics fails too. The worker of a run that timed out is
+/// told it is [`cancelled`] and given a moment to stop; one that does not check is left to finish
+/// or to be stopped when the program exits.
+fn run_one(
+    solution: Solution,
+    parsed: &Parsed,
+    timeout: Option<Duration>,
+) -> (Result<Answer>, Duration) {
+    let Some(timeout) = timeout else {
+        let now = Instant::now();
+        let result = solution(parsed);
+        return (result, now.elapsed());
     };
 
-    let now = Instant::now();
-    let result = solution();
-    let elapsed = now.elapsed();
-    match result {
-        Ok(res) => println!("Solution for part {part} completed in {elapsed:.2?}:\n{res}"),
-        Err(err) => println!("Solution for part {part} failed:\n{err}"),
+    let (sender, receiver) = mpsc::channel();
+    let parsed = Arc::clone(parsed);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let worker_cancel = Arc::clone(&cancel);
+    let start = Instant::now();
+    let spawned = thread::Builder::new()
+        .name("solution".to_string())
+        .spawn(move || {
+            // Each worker is a new thread, so this is always the first time it is set.
+            CANCEL.with(|cancel| cancel.set(worker_cancel).ok());
+            let now = Instant::now();
+            let result = solution(&parsed);
+            // The runner has stopped waiting if this fails.
+            let _ = sender.send((result, now.elapsed()));
+        });
+    if let Err(err) = spawned {
+        let err = anyhow!(err).context("failed to start a worker thread");
+        return (Err(err), start.elapsed());
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(finished) => finished,
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.store(true, Ordering::Relaxed);
+            let _ = receiver.recv_timeout(timeout.min(Duration::from_secs(1)));
+            (Err(TimedOut(timeout).into()), timeout)
+        }
+        Err(RecvTimeoutError::Disconnected) => (Err(anyhow!("solution panicked")), start.elapsed()),
     }
 }
 
+/// Parses a day's input once for all of its parts, returning it with the time parsing took, which
+/// is measured repeatedly when benchmarking.
+pub fn parse_input(day: u8, input: &str, mode: Mode) -> Result<(Parsed, Time)> {
+    let parse = || -> Result<_> {
+        let set = solution(YEAR, day).with_context(|| format!("no solution for day {day}"))?;
+        let now = Instant::now();
+        let parsed = (set.parse)(input)?;
+        let elapsed = now.elapsed();
+        let stats = match mode {
+            Mode::Once { .. } => None,
+            Mode::Bench { runs, .. } => Some(measure(runs, || (set.parse)(input))?),
+        };
+        Ok((parsed, Time { elapsed, stats }))
+    };
+    parse().with_context(|| format!("failed to parse input for day {day}"))
+}
+
+/// The selected implementations of a part. Fails if there is no solution for the part or none of
+/// its implementations match the selection.
+pub fn select_variants(
+    day: u8,
+    part: u8,
+    variants: &Variants,
+) -> Result<Vec<(&'static str, Solution)>> {
+    ensure!(part == 1 || part == 2, "part must be 1 or 2, not {part}");
+    let selected = variants.select(YEAR, day, part);
+    if selected.is_empty() {
+        let available: Vec<_> = crate::variants(YEAR, day, part)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        ensure!(!available.is_empty(), "no solution for day {day}");
+        bail!(
+            "no matching variant of day {day} part {part}; available variants are {}",
+            available.join(", ")
+        );
+    }
+    Ok(selected)
+}
+
+/// Runs and times the selected implementations of a part on the parsed input as `mode` says.
+/// Fails as [`select_variants`] does.
+pub fn run_part(
+    day: u8,
+    part: u8,
+    variants: &Variants,
+    parsed: &Parsed,
+    mode: Mode,
+) -> Result<Vec<Run>> {
+    let selected = select_variants(day, part, variants)
+        .with_context(|| format!("failed to solve day {day} part {part}"))?;
+    let runs = selected
+        .into_iter()
+        .map(|(variant, solution)| {
+            let (result, time) = match mode {
+                Mode::Once { timeout } => {
+                    let (result, elapsed) = run_one(solution, parsed, timeout);
+                    (
+                        result,
+                        Time {
+                            elapsed,
+                            stats: None,
+                        },
+                    )
+                }
+                Mode::Bench { runs, timeout } => {
+                    let (result, elapsed) = run_one(solution, parsed, timeout);
+                    let result =
+                        result.and_then(|answer| Ok((answer, measure(runs, || solution(parsed))?)));
+                    match result {
+                        Ok((answer, stats)) => (
+                            Ok(answer),
+                            Time {
+                                elapsed,
+                                stats: Some(stats),
+                            },
+                        ),
+                        Err(err) => (
+                            Err(err),
+                            Time {
+                                elapsed,
+                                stats: None,
+                            },
+                        ),
+                    }
+                }
+            };
+            let label = bench::label(Some(part), variant);
+            Run {
+                variant,
+                result: result.with_context(|| format!("failed to solve day {day} {label}")),
+                time,
+            }
+        })
+        .collect();
+    Ok(runs)
+}
+
+/// Whether every run succeeded, with the same answer.
+pub fn agree<'a>(runs: impl IntoIterator<Item = &'a Run>) -> bool {
+    let mut answers = runs.into_iter().map(|run| run.result.as_ref().ok());
+    let first = answers.next().flatten();
+    first.is_some() && answers.all(|answer| answer == first)
+}
+
 mod day1;
 mod day10;
 mod day2;
@@ -60,15 +434,128 @@ mod day7;
 mod day8;
 mod day9;
 
-pub const SOLUTIONS: &[SolutionSet] = &[
-    day1::SOLUTION,
-    day2::SOLUTION,
-    day3::SOLUTION,
-    day4::SOLUTION,
-    day5::SOLUTION,
-    day6::SOLUTION,
-    day7::SOLUTION,
-    day8::SOLUTION,
-    day9::SOLUTION,
-    day10::SOLUTION,
-];
+#[cfg(test)]
+mod test {
+    /// Each day has one solution, and variant names are unique within each part.
+    #[test]
+    fn registry() {
+        let mut days: Vec<_> = super::solutions()
+            .iter()
+            .map(|set| (set.year, set.day))
+            .collect();
+        days.dedup();
+        assert_eq!(days.len(), super::solutions().len(), "day registered twice");
+        assert_eq!(super::solution(super::YEAR, 1).unwrap().day, 1);
+        assert!(super::solution(super::YEAR, 26).is_none());
+
+        for variant in inventory::iter::<super::Variant> {
+            let names: Vec<_> = super::variants(variant.year, variant.day, variant.part)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            assert_eq!(
+                names[0],
+                super::DEFAULT_VARIANT,
+                "{} has no solution",
+                variant.name
+            );
+            let count = names.iter().filter(|name| **name == variant.name).count();
+            assert_eq!(count, 1, "{} registered more than once", variant.name);
+        }
+    }
+
+    #[test]
+    fn run_errors() {
+        use super::{driver::Mode, parse_input, run_part, Variants};
+
+        let once = Mode::Once { timeout: None };
+        let (parsed, _) = parse_input(1, "no digits", once).unwrap();
+        let err = run_part(1, 3, &Variants::Default, &parsed, once).unwrap_err();
+        assert_eq!(err.to_string(), "failed to solve day 1 part 3");
+        assert_eq!(err.root_cause().to_string(), "part must be 1 or 2, not 3");
+        let named = Variants::Named(vec!["missing".to_string()]);
+        let err = run_part(1, 1, &named, &parsed, once).unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("available variants are default"));
+        assert!(run_part(26, 1, &Variants::Default, &parsed, once).is_err());
+        assert!(parse_input(26, "", once).is_err());
+
+        let (wrong_day, _) = parse_input(9, "1 2 3", once).unwrap();
+        let runs = run_part(1, 1, &Variants::Default, &wrong_day, once).unwrap();
+        assert!(runs[0].result.is_err());
+
+        let runs = run_part(1, 1, &Variants::Default, &parsed, once).unwrap();
+        let err = runs[0].result.as_ref().unwrap_err();
+        assert_eq!(err.to_string(), "failed to solve day 1 part 1");
+        assert!(err.chain().count() > 1);
+    }
+
+    #[test]
+    fn timeout() {
+        use std::{
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+            thread,
+            time::Duration,
+        };
+
+        use anyhow::bail;
+
+        use super::{cancelled, parse_duration, run_one, Answer, Parsed, Solution, TimedOut};
+
+        assert_eq!(parse_duration("5s"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("5 days").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("0ms").is_err());
+        assert!(parse_duration("0.0000000001").is_err());
+
+        let parsed: Parsed = Arc::new(());
+        let limit = Duration::from_millis(50);
+        let fast: Solution = |_| Ok(Answer::from(1u8));
+        let slow: Solution = |_| {
+            thread::sleep(Duration::from_secs(2));
+            Ok(Answer::from(1u8))
+        };
+        let panics: Solution = |_| panic!("solution bug");
+        static STOPPED: AtomicBool = AtomicBool::new(false);
+        let cancellable: Solution = |_| {
+            while !cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            STOPPED.store(true, Ordering::Relaxed);
+            bail!("cancelled")
+        };
+
+        assert!(run_one(fast, &parsed, Some(limit)).0.is_ok());
+        let (result, elapsed) = run_one(slow, &parsed, Some(limit));
+        assert!(result.unwrap_err().is::<TimedOut>());
+        assert_eq!(elapsed, limit);
+        let (result, _) = run_one(cancellable, &parsed, Some(limit));
+        assert!(result.unwrap_err().is::<TimedOut>());
+        assert!(STOPPED.load(Ordering::Relaxed));
+        assert!(!cancelled());
+        // Reporting the panic can take longer than `limit` when a backtrace is captured.
+        let err = run_one(panics, &parsed, Some(Duration::from_secs(10)))
+            .0
+            .unwrap_err();
+        assert_eq!(err.to_string(), "solution panicked");
+    }
+
+    /// Compares the embedded inputs against the answers stored for the default profile, skipping
+    /// parts that have no stored answer.
+    #[test]
+    fn real_input() {
+        super::check::check(None, |day, part, status| {
+            assert!(!status.is_failure(), "day {day} part {part}: {status}");
+        })
+        .unwrap();
+    }
+}
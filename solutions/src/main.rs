@@ -1,13 +1,30 @@
-use aoc2023::{run_solution, SOLUTIONS};
-use clap::{arg, command, value_parser, ArgAction};
+use std::{path::PathBuf, time::Duration};
+
+use aoc2023::{
+    bench::{label, Bench},
+    check::check,
+    days,
+    driver::{drive, Mode, Text},
+    history::{compare, Environment, History, Timing},
+    input::{cache_dir, inputs_dir, invalid_inputs, timings_path, InputSource},
+    output::{run_records, Format, CSV_HEADER},
+    parse_duration,
+    scaffold::new_day,
+    submit::submit,
+    summary::{parse_days, run_all},
+    Variants, YEAR,
+};
+use aoc_client::{calendar::LAST_DAY, Profile};
+use clap::{arg, command, value_parser, ArgAction, ArgMatches, Command};
 
 fn main() {
+    let _ = dotenv::dotenv();
+
     let matches = command!()
         .arg(
             arg!(<DAY>)
-                .help("Which days solution to run")
-                .value_parser(value_parser!(u8).range(1..=(SOLUTIONS.len() as i64)))
-                .required(true),
+                .help("Which days to run: a day, a range like 1..=10, a comma-separated list, or all")
+                .value_parser(|spec: &str| parse_days(spec, &days(YEAR))),
         )
         .arg(
             arg!(--part)
@@ -17,16 +34,299 @@ fn main() {
                 .value_parser(value_parser!(u8).range(1..=2))
                 .action(ArgAction::Append),
         )
+        .arg(
+            arg!(--variant <NAME>)
+                .help("Run a named variant of each part instead of the default, or all to run every variant and check that they agree")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            arg!(--format <FORMAT>)
+                .help("How to print results: text, json (one object per line) or csv")
+                .value_parser(["text", "json", "csv"])
+                .default_value("text"),
+        )
+        .arg(
+            arg!(--bench <N>)
+                .help("Time each part N times after a warm-up, report the min, median, mean and standard deviation instead of the answer, and record them in the timing history")
+                .value_parser(value_parser!(u32).range(1..))
+                .conflicts_with("format"),
+        )
+        .arg(
+            arg!(--timeout <DURATION>)
+                .help("Give up on a part that runs longer than this, such as 5s or 500ms, and move on to the next; when benchmarking, skip parts whose first run takes longer")
+                .value_parser(parse_duration),
+        )
+        .arg(
+            arg!(--input <PATH>)
+                .short('i')
+                .help("Read input from a file, a directory of inputs, or - for stdin")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            arg!(--profile <NAME>)
+                .help("Run against a profile's input, or all for every profile")
+                .action(ArgAction::Append)
+                .conflicts_with("input"),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Submit an answer, computing it if it is not given")
+                .arg(
+                    arg!(<DAY>)
+                        .help("Which day to submit an answer for")
+                        .value_parser(value_parser!(u8).range(1..=LAST_DAY as i64)),
+                )
+                .arg(
+                    arg!(<PART>)
+                        .help("Which part to submit an answer for")
+                        .value_parser(value_parser!(u8).range(1..=2)),
+                )
+                .arg(arg!([ANSWER]).help("The answer to submit"))
+                .arg(arg!(--profile <NAME>).help("Which profile to submit as")),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Run every solution and compare against the stored answers")
+                .arg(
+                    arg!(--profile <NAME>)
+                        .help("Check a profile's input and answers, or all for every profile")
+                        .action(ArgAction::Append),
+                ),
+        )
+        .subcommand(
+            Command::new("compare")
+                .about("Benchmark the embedded inputs, compare with the timing history and record the new timings")
+                .arg(
+                    arg!([DAY])
+                        .help("Which days to compare, as for running them")
+                        .value_parser(|spec: &str| parse_days(spec, &days(YEAR)))
+                        .default_value("all"),
+                )
+                .arg(
+                    arg!(--runs <N>)
+                        .help("How many times to time each part")
+                        .value_parser(value_parser!(u32).range(1..))
+                        .default_value("10"),
+                )
+                .arg(
+                    arg!(--timeout <DURATION>)
+                        .help("Skip parts whose first run takes longer than this, such as 5s or 500ms")
+                        .value_parser(parse_duration)
+                        .default_value("1s"),
+                )
+                .arg(
+                    arg!(--threshold <PERCENT>)
+                        .help("How much slower than its baseline a part may get")
+                        .value_parser(value_parser!(f64))
+                        .default_value("10"),
+                )
+                .arg(
+                    arg!(--baseline <COMMIT>)
+                        .help("Compare with the timings from this commit instead of the latest from any other commit"),
+                ),
+        )
+        .subcommand(
+            Command::new("cache")
+                .about("List saved inputs in the build cache and the inputs directory that look like error pages")
+                .arg(arg!(--purge).help("Remove them, so that they are downloaded again")),
+        )
+        .subcommand(
+            Command::new("new")
+                .about("Create and register the module for a new day, and download its input")
+                .arg(
+                    arg!(<DAY>)
+                        .help("Which day to create")
+                        .value_parser(value_parser!(u8).range(1..=LAST_DAY as i64)),
+                ),
+        )
+        .after_help("Set RUST_BACKTRACE=1 to include a backtrace with errors from solutions.")
+        .args_conflicts_with_subcommands(true)
+        .subcommand_negates_reqs(true)
         .get_matches();
 
-    let day = *matches.get_one::<u8>("DAY").unwrap();
+    match matches.subcommand() {
+        Some(("submit", matches)) => {
+            let day = *matches.get_one::<u8>("DAY").unwrap();
+            let part = *matches.get_one::<u8>("PART").unwrap();
+            let answer = matches.get_one::<String>("ANSWER");
+            let profile = matches
+                .get_one::<String>("profile")
+                .map(|name| Profile::named(name));
+            if let Err(err) = submit(day, part, answer.map(String::as_str), profile) {
+                eprintln!("{err:#}");
+                std::process::exit(1);
+            }
+        }
+        Some(("check", matches)) => {
+            let profiles = match profiles(matches) {
+                Some(profiles) => profiles.into_iter().map(Some).collect(),
+                None => vec![None],
+            };
+            let mut failed = false;
+            for profile in profiles {
+                if let Some(profile) = &profile {
+                    println!("Profile {profile}:");
+                }
+                let result = check(profile, |day, part, status| {
+                    failed |= status.is_failure();
+                    println!("Day {day:>2} part {part}: {status}");
+                });
+                if let Err(err) = result {
+                    eprintln!("{err:#}");
+                    failed = true;
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(("compare", matches)) => {
+            let days = matches.get_one::<Vec<u8>>("DAY").unwrap();
+            let runs = *matches.get_one::<u32>("runs").unwrap() as usize;
+            let timeout = *matches.get_one::<Duration>("timeout").unwrap();
+            let threshold = *matches.get_one::<f64>("threshold").unwrap();
+            let baseline = matches.get_one::<String>("baseline");
+            match compare(days, runs, timeout, threshold, baseline.map(String::as_str)) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(err) => {
+                    eprintln!("{err:#}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(("cache", matches)) => {
+            let purge = matches.get_flag("purge");
+            let invalid = match invalid_inputs() {
+                Ok(invalid) => invalid,
+                Err(err) => {
+                    eprintln!("{err:#}");
+                    std::process::exit(1);
+                }
+            };
+            if invalid.is_empty() {
+                println!(
+                    "Every input in {} and {} looks valid",
+                    cache_dir().display(),
+                    inputs_dir().display()
+                );
+            }
+            let mut failed = false;
+            for (path, reason) in invalid {
+                if !purge {
+                    println!("{}: {reason}", path.display());
+                    failed = true;
+                } else if let Err(err) = std::fs::remove_file(&path) {
+                    eprintln!("Failed to remove {}: {err}", path.display());
+                    failed = true;
+                } else {
+                    println!("Removed {} because {reason}", path.display());
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(("new", matches)) => {
+            let day = *matches.get_one::<u8>("DAY").unwrap();
+            if let Err(err) = new_day(day) {
+                eprintln!("{err:#}");
+                std::process::exit(1);
+            }
+        }
+        _ => run(&matches),
+    }
+}
+
+/// The profiles named by `--profile`, where `all` means every profile with a session.
+fn profiles(matches: &ArgMatches) -> Option<Vec<Profile>> {
+    let names = matches.get_many::<String>("profile")?;
+    if names.clone().any(|name| name == "all") {
+        Some(Profile::all())
+    } else {
+        Some(names.map(|name| Profile::named(name)).collect())
+    }
+}
+
+fn run(matches: &ArgMatches) {
+    let days = matches.get_one::<Vec<u8>>("DAY").unwrap();
     let parts = matches
         .get_many::<u8>("part")
         .unwrap_or_default()
         .copied()
         .collect::<Vec<_>>();
+    let variants = match matches.get_many::<String>("variant") {
+        None => Variants::Default,
+        Some(names) if names.clone().any(|name| name == "all") => Variants::All,
+        Some(names) => Variants::Named(names.cloned().collect()),
+    };
+
+    let bench = matches.get_one::<u32>("bench").map(|runs| *runs as usize);
+    let timeout = matches.get_one::<Duration>("timeout").copied();
+
+    let format: Format = matches
+        .get_one::<String>("format")
+        .unwrap()
+        .parse()
+        .unwrap();
 
-    for part in parts {
-        run_solution(day, part)
+    let sources = match profiles(matches) {
+        Some(profiles) => profiles.into_iter().map(InputSource::Profile).collect(),
+        None => vec![matches
+            .get_one::<PathBuf>("input")
+            .map_or(InputSource::Embedded, |path| InputSource::from_arg(path))],
+    };
+
+    let mut failed = false;
+    if format == Format::Csv {
+        println!("{CSV_HEADER}");
+    }
+    for source in sources {
+        if format != Format::Text {
+            failed |= !run_records(days, &parts, &variants, &source, format, timeout);
+            continue;
+        }
+        if let InputSource::Profile(profile) = &source {
+            println!("Profile {profile}:");
+        }
+        if let Some(runs) = bench {
+            let env = Environment::detect();
+            let mut timings = Vec::new();
+            let mut last_day = None;
+            failed |= !drive(
+                days,
+                &parts,
+                &variants,
+                &source,
+                Mode::Bench { runs, timeout },
+                &mut Bench(|day, part, variant, stats| {
+                    if last_day.replace(day) != Some(day) {
+                        println!("Day {day}, {runs} runs each:");
+                    }
+                    println!("{:<20}  {stats}", label(part, variant));
+                    timings.push(Timing::new(&env, &source, day, part, variant, stats));
+                }),
+            );
+            if let Err(err) =
+                History::load(timings_path()).and_then(|mut history| history.record(timings))
+            {
+                eprintln!("Failed to record timings: {err:#}");
+            }
+            continue;
+        }
+        failed |= match days.len() {
+            1 => !drive(
+                days,
+                &parts,
+                &variants,
+                &source,
+                Mode::Once { timeout },
+                &mut Text,
+            ),
+            _ => !run_all(days, &parts, &variants, &source, timeout),
+        };
+    }
+    if failed {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,304 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use anyhow::Result;
+
+use crate::{
+    driver::{drive, Mode, Sink, Time},
+    input::InputSource,
+    Answer, Run, Variants, DEFAULT_VARIANT, YEAR,
+};
+
+/// How the runner prints results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Free-form text for reading in a terminal.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format '{s}'")),
+        }
+    }
+}
+
+/// The result of parsing a day's input or running one implementation of a part, for
+/// machine-readable output.
+pub struct Record {
+    pub year: u32,
+    pub day: u8,
+    /// The part, or `None` for parsing the input.
+    pub part: Option<u8>,
+    pub variant: &'static str,
+    /// Identifies the input that was used, such as `embedded` or `profile:alice`.
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub duration: Option<Duration>,
+    /// The error and its causes, outermost first.
+    pub error: Vec<String>,
+}
+
+pub const CSV_HEADER: &str = "year,day,part,variant,input,answer,duration_ns,error";
+
+impl Record {
+    pub fn json(&self) -> String {
+        let answer = match &self.answer {
+            Some(answer @ (Answer::Int(_) | Answer::Unsigned(_))) => answer.to_string(),
+            Some(answer) => json_string(&answer.to_string()),
+            None => "null".to_string(),
+        };
+        let part = self
+            .part
+            .map_or("null".to_string(), |part| part.to_string());
+        let duration = self.duration.map_or("null".to_string(), |duration| {
+            duration.as_nanos().to_string()
+        });
+        let error = if self.error.is_empty() {
+            "null".to_string()
+        } else {
+            let causes: Vec<_> = self.error.iter().map(|cause| json_string(cause)).collect();
+            format!("[{}]", causes.join(","))
+        };
+        format!(
+            r#"{{"year":{},"day":{},"part":{part},"variant":{},"input":{},"answer":{answer},"duration_ns":{duration},"error":{error}}}"#,
+            self.year,
+            self.day,
+            json_string(self.variant),
+            json_string(&self.input),
+        )
+    }
+
+    /// One CSV row matching [`CSV_HEADER`], with an empty part for parsing. The error chain is
+    /// joined with `: `.
+    pub fn csv(&self) -> String {
+        let answer = self
+            .answer
+            .as_ref()
+            .map(Answer::to_string)
+            .unwrap_or_default();
+        let duration = self
+            .duration
+            .map(|duration| duration.as_nanos().to_string())
+            .unwrap_or_default();
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.map(|part| part.to_string()).unwrap_or_default(),
+            self.variant.to_string(),
+            self.input.clone(),
+            answer,
+            duration,
+            self.error.join(": "),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// Parses the input from `source` for each day and runs the selected parts against it, printing a
+/// record for the parsing and for each part in `format`, which must not be [`Format::Text`]. Parts
+/// that take longer than `timeout` are recorded with a timeout error.
+/// Returns whether every day parsed, every part succeeded and, where several variants were run,
+/// whether they agreed.
+pub fn run_records(
+    days: &[u8],
+    parts: &[u8],
+    variants: &Variants,
+    source: &InputSource,
+    format: Format,
+    timeout: Option<Duration>,
+) -> bool {
+    let mut records = Records {
+        parts,
+        source,
+        format,
+    };
+    drive(
+        days,
+        parts,
+        variants,
+        source,
+        Mode::Once { timeout },
+        &mut records,
+    )
+}
+
+/// Prints a record for each step as soon as it finishes.
+struct Records<'a> {
+    parts: &'a [u8],
+    source: &'a InputSource,
+    format: Format,
+}
+
+impl Records<'_> {
+    fn record(&self, day: u8, part: Option<u8>, variant: &'static str) -> Record {
+        Record {
+            year: YEAR,
+            day,
+            part,
+            variant,
+            input: self.source.to_string(),
+            answer: None,
+            duration: None,
+            error: Vec::new(),
+        }
+    }
+
+    fn print(&self, record: Record) {
+        match self.format {
+            Format::Json => println!("{}", record.json()),
+            Format::Csv => println!("{}", record.csv()),
+            Format::Text => unreachable!("text output is not made of records"),
+        }
+    }
+}
+
+/// The error and its causes, outermost first.
+fn chain(err: &anyhow::Error) -> Vec<String> {
+    err.chain().map(ToString::to_string).collect()
+}
+
+impl Sink for Records<'_> {
+    fn no_input(&mut self, day: u8, err: anyhow::Error) {
+        for part in self.parts.iter().copied() {
+            self.print(Record {
+                error: chain(&err),
+                ..self.record(day, Some(part), DEFAULT_VARIANT)
+            });
+        }
+    }
+
+    fn parsed(&mut self, day: u8, result: Result<Time>) {
+        let record = self.record(day, None, DEFAULT_VARIANT);
+        self.print(match result {
+            Ok(time) => Record {
+                duration: Some(time.elapsed),
+                ..record
+            },
+            Err(err) => Record {
+                error: chain(&err),
+                ..record
+            },
+        });
+    }
+
+    fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>) {
+        let runs = match runs {
+            Ok(runs) => runs,
+            Err(err) => {
+                return self.print(Record {
+                    error: chain(&err),
+                    ..self.record(day, Some(part), DEFAULT_VARIANT)
+                })
+            }
+        };
+        for run in runs {
+            let (answer, error) = match run.result {
+                Ok(answer) => (Some(answer), Vec::new()),
+                Err(err) => (None, chain(&err)),
+            };
+            self.print(Record {
+                answer,
+                duration: Some(run.time.elapsed),
+                error,
+                ..self.record(day, Some(part), run.variant)
+            });
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Quotes a field if it contains a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::Record;
+    use crate::Answer;
+
+    fn record(answer: Option<Answer>, error: &[&str]) -> Record {
+        Record {
+            year: 2023,
+            day: 5,
+            part: Some(2),
+            variant: "default",
+            input: "embedded".to_string(),
+            answer,
+            duration: Some(Duration::from_micros(12)),
+            error: error.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            record(Some(Answer::from(46u32)), &[]).json(),
+            r#"{"year":2023,"day":5,"part":2,"variant":"default","input":"embedded","answer":46,"duration_ns":12000,"error":null}"#
+        );
+        let grid = record(Some(Answer::grid(["x\"", "\\."])), &[]).json();
+        assert!(grid.contains(r#""answer":"x\"\n\\.""#));
+        let error = record(None, &["failed to parse", "invalid digit"]).json();
+        assert!(error.contains(r#""answer":null"#));
+        assert!(error.contains(r#""error":["failed to parse","invalid digit"]"#));
+        let parse = Record {
+            part: None,
+            ..record(None, &[])
+        };
+        assert!(parse.json().contains(r#""part":null"#));
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            record(Some(Answer::from(46u32)), &[]).csv(),
+            "2023,5,2,default,embedded,46,12000,"
+        );
+        assert_eq!(
+            record(Some(Answer::from("a,\"b\"")), &["x"]).csv(),
+            "2023,5,2,default,embedded,\"a,\"\"b\"\"\",12000,x"
+        );
+        let parse = Record {
+            part: None,
+            ..record(None, &[])
+        };
+        assert_eq!(parse.csv(), "2023,5,,default,embedded,,12000,");
+    }
+}
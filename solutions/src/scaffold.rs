@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc_client::{
+    puzzle::{cached_puzzle, Puzzle},
+    AocClient, Profile,
+};
+
+use crate::{
+    input::{cache_dir, inputs_dir, InputSource},
+    YEAR,
+};
+
+/// Creates `src/day{day}.rs` from a template, registers it in `src/lib.rs` and downloads its input
+/// for the profile selected by `AOC_PROFILE` to where `include_aoc!` finds it. Fails if the input
+/// cannot be found or downloaded, after creating the module.
+///
+/// The first example and its answer are written into the module's test rather than embedded with
+/// `include_aoc_example!`, so that the new day builds without the puzzle page, such as with
+/// `AOC_OFFLINE`. If they cannot be found, the module is still created with empty ones to fill in.
+///
+/// The new day is only available to the runner once it has been rebuilt.
+pub fn new_day(day: u8) -> Result<()> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let module_path = src.join(format!("day{day}.rs"));
+    ensure!(
+        !module_path.exists(),
+        "{} already exists",
+        module_path.display()
+    );
+
+    let lib_path = src.join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path)
+        .with_context(|| format!("failed to read {}", lib_path.display()))?;
+    let lib = register(&lib, day)?;
+
+    let profile = Profile::from_env();
+    let example = first_example(day, &profile);
+    let (input, answer) = example
+        .as_ref()
+        .map_or(("", ""), |(input, answer)| (input, answer));
+    std::fs::write(&module_path, template(day, input, answer))
+        .with_context(|| format!("failed to write {}", module_path.display()))?;
+    std::fs::write(&lib_path, lib)
+        .with_context(|| format!("failed to write {}", lib_path.display()))?;
+    println!(
+        "Created {} and registered it in lib.rs",
+        module_path.display()
+    );
+
+    let example = example.context("failed to get the first example; fill it in by hand");
+    let input = InputSource::Profile(profile).load(day);
+    match (&input, &example) {
+        (Ok(_), _) => println!("The input for day {day} is ready"),
+        // Only one error can be returned, so report the other one here.
+        (Err(_), Err(err)) => eprintln!("{err:#}"),
+        (Err(_), Ok(_)) => {}
+    }
+    input.with_context(|| format!("failed to download the input for day {day}"))?;
+    example.map(|_| ())
+}
+
+/// The first example in the puzzle description and its answer for part 1, from the page saved in
+/// the inputs directory or the build cache, downloading the page if neither has it.
+fn first_example(day: u8, profile: &Profile) -> Result<(String, String)> {
+    let day = day as u32;
+    let mut path = profile.project_puzzle_path(&inputs_dir(), YEAR, day);
+    if !path.exists() {
+        let cache_dir = cache_dir();
+        let page_path = cache_dir.join(profile.puzzle_file_name(YEAR, day));
+        let client = AocClient::from_env(profile)?;
+        path = cached_puzzle(
+            &cache_dir,
+            &page_path,
+            YEAR,
+            day,
+            &client,
+            aoc_client::offline(),
+            false,
+        )?
+        .context("the puzzle page is not cached and AOC_OFFLINE is set")?;
+    }
+    let page = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let puzzle = Puzzle::parse(&page);
+    let example = puzzle.examples.into_iter().next();
+    let answer = puzzle.answers.into_iter().next().flatten();
+    example
+        .zip(answer)
+        .with_context(|| format!("no example with an answer in {}", path.display()))
+}
+
+/// The module for a new day, with the input split into lines, part 1 stubbed out and an ignored
+/// test against the first example.
+fn template(day: u8, example: &str, answer: &str) -> String {
+    format!(
+        r#"use anyhow::{{bail, Result}};
+use include_aoc::include_aoc;
+
+static INPUT: &str = include_aoc!({YEAR}, {day});
+
+solution!({YEAR}, {day}, INPUT, parse, pt1);
+
+fn parse(input: &str) -> Result<Vec<String>> {{
+    Ok(input.lines().map(str::to_string).collect())
+}}
+
+fn pt1(_lines: &[String]) -> Result<u64> {{
+    bail!("Solution for part 1 not yet implemented")
+}}
+
+#[cfg(test)]
+mod test {{
+    const EXAMPLE: &str = {example:?};
+
+    #[test]
+    #[ignore = "part 1 is not implemented yet"]
+    fn pt1() {{
+        let input = super::parse(EXAMPLE).unwrap();
+        assert_eq!(super::pt1(&input).unwrap().to_string(), {answer:?});
+    }}
+}}
+"#
+    )
+}
+
+/// Adds `mod day{day};` to the source of lib.rs; the module registers its own solution.
+fn register(lib: &str, day: u8) -> Result<String> {
+    let module = format!("mod day{day};");
+    // Keep the module declarations in the order rustfmt sorts them.
+    let mut lines: Vec<_> = lib.lines().collect();
+    let is_day = |line: &&str| line.starts_with("mod day") && line.ends_with(';');
+    let Some(first) = lines.iter().position(is_day) else {
+        bail!("could not find the day modules in lib.rs");
+    };
+    let last = first
+        + lines[first..]
+            .iter()
+            .take_while(|line| is_day(line))
+            .count();
+    lines.insert(last, module.as_str());
+    lines[first..=last].sort_by_key(|line| line.trim_end_matches(';'));
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    const LIB: &str = "pub mod check;
+
+mod day1;
+mod day2;
+mod day9;
+
+#[cfg(test)]
+mod test {}
+";
+
+    #[test]
+    fn template() {
+        let module = super::template(11, "a \"b\"\n", "42");
+        assert!(module.contains("const EXAMPLE: &str = \"a \\\"b\\\"\\n\";"));
+        assert!(module.contains("assert_eq!(super::pt1(&input).unwrap().to_string(), \"42\");"));
+    }
+
+    #[test]
+    fn register() {
+        let lib = super::register(LIB, 12).unwrap();
+        assert_eq!(
+            lib,
+            "pub mod check;
+
+mod day1;
+mod day12;
+mod day2;
+mod day9;
+
+#[cfg(test)]
+mod test {}
+"
+        );
+    }
+}
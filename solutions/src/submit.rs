@@ -0,0 +1,69 @@
+use anyhow::{bail, Context, Result};
+use aoc_client::{
+    answers::Answers,
+    submit::{Attempt, Submissions, Verdict},
+    AocClient, Profile,
+};
+
+use crate::{
+    input::{answers_path, cache_dir, submissions_path, InputSource},
+    solution, Answer, YEAR,
+};
+
+/// Submits an answer for a part, computing it from the profile's input if it is not given.
+///
+/// Every attempt is recorded in `submissions.tsv` next to the profile's inputs, and answers that
+/// are known to be wrong, or that fall outside the bounds given by earlier too high and too low
+/// verdicts, are refused without contacting the site. Correct answers are also saved to
+/// `answers.tsv` for `check`.
+pub fn submit(day: u8, part: u8, answer: Option<&str>, profile: Option<Profile>) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => {
+            let source = profile
+                .clone()
+                .map_or(InputSource::Embedded, InputSource::Profile);
+            let input = source.load(day)?;
+            let set = solution(YEAR, day).with_context(|| format!("no solution for day {day}"))?;
+            let parsed = (set.parse)(&input).context("failed to parse input")?;
+            let solution = if part == 1 { set.part1 } else { set.part2 };
+            match solution(&parsed).context("failed to compute answer")? {
+                Answer::Grid(rows) => bail!(
+                    "read the answer off the grid and submit it by hand:\n{}",
+                    rows.join("\n")
+                ),
+                answer => answer.to_string(),
+            }
+        }
+    };
+    let profile = profile.unwrap_or_default();
+
+    let mut submissions = Submissions::load(submissions_path(&profile))?;
+    if let Err(reason) = submissions.check(day as u32, part, &answer) {
+        bail!("not submitting {answer} for day {day} part {part}: {reason}");
+    }
+
+    let verdict =
+        AocClient::from_env(&profile)?.submit(&cache_dir(), YEAR, day as u32, part, &answer)?;
+    submissions.record(Attempt {
+        day: day as u32,
+        part,
+        answer: answer.clone(),
+        verdict: verdict.clone(),
+    })?;
+
+    match verdict {
+        Verdict::Correct => {
+            Answers::load(answers_path(&profile))?.set(day as u32, part, &answer)?;
+            println!("{answer} is correct for day {day} part {part}")
+        }
+        Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong => {
+            println!("{answer} is {verdict} for day {day} part {part}")
+        }
+        Verdict::Wait(wait) => println!("Answer not checked; wait {wait} before submitting again"),
+        Verdict::AlreadySolved => {
+            println!("Answer not checked; day {day} part {part} is already solved or locked")
+        }
+    }
+    Ok(())
+}
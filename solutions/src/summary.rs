@@ -0,0 +1,252 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::{
+    agree,
+    driver::{disagreement, drive, print_error, Mode, Sink, Time},
+    input::InputSource,
+    timed_out, Answer, Run, Variants, DEFAULT_VARIANT,
+};
+
+/// Parses which days to run: `all`, a single day, a range like `1..=10` or `3..7`, or a
+/// comma-separated list of those. Days without a solution are left out of ranges and `all`, but
+/// naming one on its own is an error.
+pub fn parse_days(spec: &str, solved: &[u8]) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+    for item in spec.split(',').map(str::trim) {
+        if item == "all" {
+            days.extend_from_slice(solved);
+            continue;
+        }
+        let (start, end) = if let Some((start, end)) = item.split_once("..=") {
+            (parse_day(start)?, parse_day(end)?)
+        } else if let Some((start, end)) = item.split_once("..") {
+            let end = parse_day(end)?;
+            (parse_day(start)?, end.saturating_sub(1))
+        } else {
+            let day = parse_day(item)?;
+            if !solved.contains(&day) {
+                let solved: Vec<_> = solved.iter().map(u8::to_string).collect();
+                return Err(format!(
+                    "no solution for day {day}; solved days are {}",
+                    solved.join(", ")
+                ));
+            }
+            (day, day)
+        };
+        days.extend((start..=end).filter(|day| solved.contains(day)));
+    }
+    days.sort();
+    days.dedup();
+    if days.is_empty() {
+        return Err(format!("no solved days in '{spec}'"));
+    }
+    Ok(days)
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.trim()
+        .parse()
+        .map_err(|_| format!("'{day}' is not a day"))
+}
+
+/// One line of the summary table.
+struct Row {
+    day: u8,
+    /// The part, or `None` for parsing the day's input.
+    part: Option<u8>,
+    variant: &'static str,
+    answer: String,
+    elapsed: Option<Duration>,
+    status: &'static str,
+}
+
+/// Parses the input from `source` for each day and runs the selected parts against it, then prints
+/// a table of the answers, times and statuses, followed by the total time and any errors. Parts
+/// that take longer than `timeout` are marked as timed out and the rest carry on. Returns whether
+/// every day parsed, every part succeeded and, where several variants were run, whether they
+/// agreed.
+pub fn run_all(
+    days: &[u8],
+    parts: &[u8],
+    variants: &Variants,
+    source: &InputSource,
+    timeout: Option<Duration>,
+) -> bool {
+    let start = Instant::now();
+    let mut summary = Summary {
+        parts,
+        rows: Vec::new(),
+        errors: Vec::new(),
+    };
+    let ok = drive(
+        days,
+        parts,
+        variants,
+        source,
+        Mode::Once { timeout },
+        &mut summary,
+    );
+
+    print_table(&summary.rows);
+    let failed = summary.rows.iter().filter(|row| row.status != "ok").count();
+    let solved = summary.rows.iter().filter(|row| row.part.is_some()).count();
+    println!(
+        "Total: {:.2?} for {solved} parts, {failed} failed",
+        start.elapsed()
+    );
+    for err in &summary.errors {
+        print_error(err);
+    }
+    ok
+}
+
+/// Collects a row for each step, and the errors to print after the table.
+struct Summary<'a> {
+    parts: &'a [u8],
+    rows: Vec<Row>,
+    errors: Vec<anyhow::Error>,
+}
+
+impl Sink for Summary<'_> {
+    fn no_input(&mut self, day: u8, err: anyhow::Error) {
+        for part in self.parts.iter().copied() {
+            self.rows
+                .push(Row::failed(day, Some(part), DEFAULT_VARIANT, "no input"));
+        }
+        self.errors.push(err);
+    }
+
+    fn parsed(&mut self, day: u8, result: Result<Time>) {
+        match result {
+            Ok(time) => self.rows.push(Row {
+                day,
+                part: None,
+                variant: DEFAULT_VARIANT,
+                answer: String::new(),
+                elapsed: Some(time.elapsed),
+                status: "ok",
+            }),
+            Err(err) => {
+                self.rows
+                    .push(Row::failed(day, None, DEFAULT_VARIANT, "error"));
+                self.errors.push(err);
+            }
+        }
+    }
+
+    fn part(&mut self, day: u8, part: u8, runs: Result<Vec<Run>>) {
+        let runs = match runs {
+            Ok(runs) => runs,
+            Err(err) => {
+                self.rows
+                    .push(Row::failed(day, Some(part), DEFAULT_VARIANT, "error"));
+                self.errors.push(err);
+                return;
+            }
+        };
+        let agree = agree(&runs);
+        self.errors.extend(disagreement(day, part, &runs));
+        for run in runs {
+            let timed_out = timed_out(&run);
+            let row = match run.result {
+                Ok(answer) => Row {
+                    day,
+                    part: Some(part),
+                    variant: run.variant,
+                    answer: short_answer(&answer),
+                    elapsed: Some(run.time.elapsed),
+                    status: if agree { "ok" } else { "DISAGREE" },
+                },
+                Err(err) => {
+                    self.errors.push(err);
+                    match timed_out {
+                        true => Row {
+                            elapsed: Some(run.time.elapsed),
+                            ..Row::failed(day, Some(part), run.variant, "timeout")
+                        },
+                        false => Row::failed(day, Some(part), run.variant, "error"),
+                    }
+                }
+            };
+            self.rows.push(row);
+        }
+    }
+}
+
+impl Row {
+    fn failed(day: u8, part: Option<u8>, variant: &'static str, status: &'static str) -> Self {
+        Row {
+            day,
+            part,
+            variant,
+            answer: String::new(),
+            elapsed: None,
+            status,
+        }
+    }
+}
+
+/// The answer on a single line, since grids do not fit in a table.
+fn short_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Grid(rows) => format!("({}-row grid)", rows.len()),
+        answer => answer.to_string(),
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let parts: Vec<_> = rows
+        .iter()
+        .map(|row| match (row.part, row.variant) {
+            (None, _) => "parse".to_string(),
+            (Some(part), DEFAULT_VARIANT) => part.to_string(),
+            (Some(part), variant) => format!("{part} ({variant})"),
+        })
+        .collect();
+    let times: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            row.elapsed
+                .map_or(String::new(), |elapsed| format!("{elapsed:.2?}"))
+        })
+        .collect();
+    let part_width = parts.iter().map(String::len).max().unwrap_or(0).max(4);
+    let answer_width = rows
+        .iter()
+        .map(|row| row.answer.len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let time_width = times.iter().map(String::len).max().unwrap_or(0).max(4);
+
+    println!(
+        "Day  {:<part_width$}  {:<answer_width$}  {:>time_width$}  Status",
+        "Part", "Answer", "Time"
+    );
+    for ((row, part), time) in rows.iter().zip(parts).zip(times) {
+        println!(
+            "{:>3}  {part:<part_width$}  {:<answer_width$}  {time:>time_width$}  {}",
+            row.day, row.answer, row.status
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_days;
+
+    #[test]
+    fn days() {
+        let solved = [1, 2, 3, 5, 10];
+        assert_eq!(parse_days("all", &solved).unwrap(), solved);
+        assert_eq!(parse_days("2", &solved).unwrap(), [2]);
+        assert_eq!(parse_days("1..=5", &solved).unwrap(), [1, 2, 3, 5]);
+        assert_eq!(parse_days("1..5", &solved).unwrap(), [1, 2, 3]);
+        assert_eq!(parse_days("10, 1..3", &solved).unwrap(), [1, 2, 10]);
+        assert!(parse_days("4", &solved).is_err());
+        assert!(parse_days("6..=9", &solved).is_err());
+        assert!(parse_days("x", &solved).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+/// Generates inputs for a day of increasing size, registered with `generator!`, for measuring how
+/// its solutions scale.
+pub struct Generator {
+    pub year: u32,
+    pub day: u8,
+    /// Generates an input with `size` records, such as lines or games, in the puzzle's format.
+    pub generate: fn(usize) -> String,
+}
+
+inventory::collect!(Generator);
+
+/// The input generator for a day, if it has one.
+pub fn generator(year: u32, day: u8) -> Option<&'static Generator> {
+    inventory::iter::<Generator>
+        .into_iter()
+        .find(|generator| generator.year == year && generator.day == day)
+}
+
+/// A small xorshift generator, so that synthetic inputs are the same on every run.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // The state must never be zero.
+        Rng(seed | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len() as u64) as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    /// Every generated input parses and both parts solve it.
+    #[test]
+    fn generators() {
+        for generator in inventory::iter::<super::Generator> {
+            let input = (generator.generate)(100);
+            let set = crate::solution(generator.year, generator.day).unwrap();
+            let parsed = (set.parse)(&input).unwrap();
+            (set.part1)(&parsed).unwrap();
+            (set.part2)(&parsed).unwrap();
+        }
+    }
+}